@@ -0,0 +1,55 @@
+//! Compares `Arc::from_header_and_trusted_len_iter` with the checked
+//! `Arc::from_header_and_iter`, building a million-element slice.
+
+extern crate triomphe;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use triomphe::{Arc, HeaderSlice};
+
+const LEN: usize = 1_000_000;
+const ITERATIONS: u32 = 200;
+
+/// Returns the fastest of a few averaged runs of `f`.
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..ITERATIONS {
+                f();
+            }
+            start.elapsed() / ITERATIONS
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let checked = time(|| {
+        let arc = Arc::from_header_and_iter((), 0..black_box(LEN));
+        black_box::<Arc<HeaderSlice<(), [usize]>>>(arc);
+    });
+    let trusted = time(|| {
+        let arc = Arc::from_header_and_trusted_len_iter((), 0..black_box(LEN));
+        black_box::<Arc<HeaderSlice<(), [usize]>>>(arc);
+    });
+    println!(
+        "range  checked: {:>10?}  trusted: {:>10?}",
+        checked, trusted
+    );
+
+    let items: Vec<u64> = (0..LEN as u64).collect();
+    let checked = time(|| {
+        let arc = Arc::from_header_and_iter((), black_box(&items).iter().map(|x| x * 3));
+        black_box(arc);
+    });
+    let trusted = time(|| {
+        let arc =
+            Arc::from_header_and_trusted_len_iter((), black_box(&items).iter().map(|x| x * 3));
+        black_box(arc);
+    });
+    println!(
+        "map    checked: {:>10?}  trusted: {:>10?}",
+        checked, trusted
+    );
+}
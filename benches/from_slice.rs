@@ -0,0 +1,50 @@
+//! Compares `Arc::<[T]>::from(&[T])`, which clones the elements, with the
+//! `memcpy` of `Arc::from_header_and_slice` for `Copy` elements.
+
+extern crate triomphe;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use triomphe::Arc;
+
+const LEN: usize = 1_000_000;
+const ITERATIONS: u32 = 200;
+
+/// Returns the fastest of a few averaged runs of `f`.
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..ITERATIONS {
+                f();
+            }
+            start.elapsed() / ITERATIONS
+        })
+        .min()
+        .unwrap()
+}
+
+fn compare<T: Copy>(name: &str, items: &[T]) {
+    let cloned = time(|| {
+        black_box(Arc::<[T]>::from(black_box(items)));
+    });
+    let copied = time(|| {
+        let arc = Arc::from_header_and_slice((), black_box(items));
+        black_box(Arc::<[T]>::from(arc));
+    });
+    println!(
+        "{:<8} From<&[T]>: {:>10?}  memcpy: {:>10?}",
+        name, cloned, copied
+    );
+}
+
+fn main() {
+    let bytes: Vec<u8> = (0..LEN).map(|i| i as u8).collect();
+    compare("u8", &bytes);
+
+    let words: Vec<u64> = (0..LEN as u64).collect();
+    compare("u64", &words);
+
+    let pairs: Vec<(u32, u16)> = (0..LEN as u32).map(|i| (i, i as u16)).collect();
+    compare("(u32, u16)", &pairs);
+}
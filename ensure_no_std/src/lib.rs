@@ -0,0 +1,26 @@
+//! Uses the allocating and panicking paths of triomphe from a `no_std` crate.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use triomphe::{Arc, ThinArc, UniqueArc};
+
+pub fn arc(value: u32) -> u32 {
+    let mut arc = Arc::new(value);
+    *Arc::make_mut(&mut arc) += 1;
+    Arc::try_unwrap(arc).unwrap_or(0)
+}
+
+pub fn header_slice(items: &[u32]) -> usize {
+    let arc = Arc::from_header_with_length_and_iter(1u8, items.iter().copied());
+    let thin = Arc::into_thin(arc);
+    let thin = ThinArc::from_header_and_slice(*thin.header(), thin.slice());
+    thin.len()
+}
+
+pub fn slice(items: Vec<u32>) -> Arc<[u32]> {
+    let unique: UniqueArc<[u32]> = items.into_iter().collect();
+    unique.shareable()
+}
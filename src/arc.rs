@@ -1,6 +1,10 @@
 use alloc::alloc::handle_alloc_error;
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::{Allocator, Global};
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 use core::alloc::Layout;
+use core::any::Any;
 use core::borrow;
 use core::cmp::Ordering;
 use core::convert::From;
@@ -9,24 +13,29 @@ use core::fmt;
 use core::hash::{Hash, Hasher};
 use core::iter::FromIterator;
 use core::marker::PhantomData;
-use core::mem::{ManuallyDrop, MaybeUninit};
+#[cfg(feature = "nightly")]
+use core::marker::Unsize;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
 use core::ops::Deref;
+#[cfg(feature = "nightly")]
+use core::ops::{CoerceUnsized, DispatchFromDyn};
+use core::pin::Pin;
 use core::ptr::{self, NonNull};
-use core::sync::atomic;
-use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "stable_deref_trait")]
 use stable_deref_trait::{CloneStableDeref, StableDeref};
 
+use crate::atomic;
+use crate::atomic::Ordering::{Acquire, Relaxed, Release};
 use crate::{abort, ArcBorrow, HeaderSlice, OffsetArc, UniqueArc};
 
 /// A soft limit on the amount of references that may be made to an `Arc`.
 ///
 /// Going above this limit will abort your program (although not
 /// necessarily) at _exactly_ `MAX_REFCOUNT + 1` references.
-const MAX_REFCOUNT: usize = (isize::MAX) as usize;
+pub(crate) const MAX_REFCOUNT: usize = (isize::MAX) as usize;
 
 /// The object allocated by an `Arc<T>`
 #[repr(C)]
@@ -58,21 +67,132 @@ impl<T: ?Sized> ArcInner<T> {
     }
 }
 
+// The orderings used to update the refcount are what makes sharing an `Arc` across
+// threads sound: `Relaxed` increments, `Release` decrements, and an `Acquire` load
+// before the data is dropped (or handed out uniquely). Keep them in these helpers,
+// which are covered by the `loom_` tests.
+//
+// They only borrow the count, not the whole `ArcInner`: another thread may free
+// the allocation as soon as our decrement is done, which would be UB while a
+// `&ArcInner<T>` argument is still live.
+
+/// Increments the reference count, aborting if it overflows.
+#[inline]
+pub(crate) fn increment(count: &atomic::AtomicUsize) {
+    // Using a relaxed ordering is alright here, as knowledge of the
+    // original reference prevents other threads from erroneously deleting
+    // the object.
+    //
+    // As explained in the [Boost documentation][1], Increasing the
+    // reference counter can always be done with memory_order_relaxed: New
+    // references to an object can only be formed from an existing
+    // reference, and passing an existing reference from one thread to
+    // another must already provide any required synchronization.
+    //
+    // [1]: (www.boost.org/doc/libs/1_55_0/doc/html/atomic/usage_examples.html)
+    let old_size = count.fetch_add(1, Relaxed);
+
+    // However we need to guard against massive refcounts in case someone
+    // is `mem::forget`ing Arcs. If we don't do this the count can overflow
+    // and users will use-after free. We racily saturate to `isize::MAX` on
+    // the assumption that there aren't ~2 billion threads incrementing
+    // the reference count at once. This branch will never be taken in
+    // any realistic program.
+    //
+    // We abort because such a program is incredibly degenerate, and we
+    // don't care to support it.
+    if old_size > MAX_REFCOUNT {
+        abort();
+    }
+}
+
+/// Decrements the reference count, returning `true` if this was the last
+/// reference, in which case the data can be dropped.
+#[inline]
+pub(crate) fn decrement(count: &atomic::AtomicUsize) -> bool {
+    // Because `fetch_sub` is already atomic, we do not need to synchronize
+    // with other threads unless we are going to delete the object.
+    if count.fetch_sub(1, Release) != 1 {
+        return false;
+    }
+
+    // FIXME(bholley): Use the updated comment when [2] is merged.
+    //
+    // This load is needed to prevent reordering of use of the data and
+    // deletion of the data.  Because it is marked `Release`, the decreasing
+    // of the reference count synchronizes with this `Acquire` load. This
+    // means that use of the data happens before decreasing the reference
+    // count, which happens before this load, which happens before the
+    // deletion of the data.
+    //
+    // As explained in the [Boost documentation][1],
+    //
+    // > It is important to enforce any possible access to the object in one
+    // > thread (through an existing reference) to *happen before* deleting
+    // > the object in a different thread. This is achieved by a "release"
+    // > operation after dropping a reference (any access to the object
+    // > through this reference must obviously happened before), and an
+    // > "acquire" operation before deleting the object.
+    //
+    // [1]: (www.boost.org/doc/libs/1_55_0/doc/html/atomic/usage_examples.html)
+    // [2]: https://github.com/rust-lang/rust/pull/41714
+    count.load(Acquire);
+    true
+}
+
+/// Computes the layout of an `ArcInner` whose `data` field has the given layout.
+pub(crate) fn layout_for_value_layout(value_layout: Layout) -> Layout {
+    try_layout_for_value_layout(value_layout).expect("capacity overflow")
+}
+
+/// Like [`layout_for_value_layout`], but returns `None` if the size overflows.
+fn try_layout_for_value_layout(value_layout: Layout) -> Option<Layout> {
+    let (layout, _) = Layout::new::<ArcInner<()>>().extend(value_layout).ok()?;
+    Some(layout.pad_to_align())
+}
+
+/// The error type returned by fallible allocating constructors such as
+/// [`Arc::try_new`], indicating that the allocator failed to provide memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
+
 /// An atomically reference counted shared pointer
 ///
 /// See the documentation for [`Arc`] in the standard library. Unlike the
 /// standard library `Arc`, this `Arc` does not support weak reference counting.
 ///
 /// [`Arc`]: https://doc.rust-lang.org/stable/std/sync/struct.Arc.html
-#[repr(transparent)]
-pub struct Arc<T: ?Sized> {
+///
+/// With the `allocator_api` feature, an `Arc` can be allocated in a custom
+/// `Allocator` with `Arc::new_in`. Most of the API is only available for
+/// the `Global` allocator though.
+#[cfg_attr(not(feature = "allocator_api"), repr(transparent))]
+pub struct Arc<T: ?Sized, #[cfg(feature = "allocator_api")] A: Allocator = Global> {
     pub(crate) p: ptr::NonNull<ArcInner<T>>,
     pub(crate) phantom: PhantomData<T>,
+    #[cfg(feature = "allocator_api")]
+    pub(crate) alloc: A,
 }
 
+#[cfg(not(feature = "allocator_api"))]
 unsafe impl<T: ?Sized + Sync + Send> Send for Arc<T> {}
+#[cfg(not(feature = "allocator_api"))]
 unsafe impl<T: ?Sized + Sync + Send> Sync for Arc<T> {}
 
+#[cfg(feature = "allocator_api")]
+unsafe impl<T: ?Sized + Sync + Send, A: Allocator + Send> Send for Arc<T, A> {}
+#[cfg(feature = "allocator_api")]
+unsafe impl<T: ?Sized + Sync + Send, A: Allocator + Sync> Sync for Arc<T, A> {}
+
 impl<T> Arc<T> {
     /// Construct an `Arc<T>`
     #[inline]
@@ -82,11 +202,33 @@ impl<T> Arc<T> {
             data,
         }));
 
+        unsafe { Arc::from_inner(ptr::NonNull::new_unchecked(ptr)) }
+    }
+
+    /// Construct a new `Pin<Arc<T>>`. If `T` does not implement `Unpin`, then
+    /// `data` will be pinned in memory and unable to be moved.
+    #[inline]
+    pub fn pin(data: T) -> Pin<Arc<T>> {
+        // Safety: `Arc` never moves its data, and `Pin<Arc<T>>` only gives shared access to it
+        unsafe { Pin::new_unchecked(Arc::new(data)) }
+    }
+
+    /// Construct an `Arc<T>`, returning an error if the allocation fails.
+    ///
+    /// Unlike [`Arc::new`], this does not call [`handle_alloc_error`] on failure.
+    ///
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    #[inline]
+    pub fn try_new(data: T) -> Result<Self, AllocError> {
         unsafe {
-            Arc {
-                p: ptr::NonNull::new_unchecked(ptr),
-                phantom: PhantomData,
-            }
+            // Safety: the closure only changes the type of the pointer
+            let inner =
+                Self::try_allocate_for_layout(Layout::new::<T>(), |mem| mem as *mut ArcInner<T>)?;
+
+            // Safety: `inner` is valid for writes (just allocated)
+            ptr::write(ptr::addr_of_mut!((*inner.as_ptr()).data), data);
+
+            Ok(Arc::from_inner(inner))
         }
     }
 
@@ -131,6 +273,10 @@ impl<T> Arc<T> {
     /// Otherwise, an [`Err`] is returned with the same [`Arc`] that was
     /// passed in.
     ///
+    /// The refcount is loaded with `Acquire` ordering, so that accesses through
+    /// `Arc`s dropped on other threads happen before any mutation through the
+    /// returned `UniqueArc`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -184,7 +330,9 @@ impl<T> Arc<T> {
         let this = ManuallyDrop::new(this);
 
         // Perform the same logic as `drop_inner` to ensure that the reference count is decremented.
-        if this.inner().count.fetch_sub(1, Release) != 1 {
+        // This also synchronizes with the decrements of the other holders, so that their uses of
+        // the data happen before we hand out unique access to it.
+        if !decrement(&this.inner().count) {
             return None;
         }
 
@@ -196,6 +344,50 @@ impl<T> Arc<T> {
         // `ManuallyDrop` is safe as we know we're the only owners at this point.
         Some(unsafe { UniqueArc::from_arc(ManuallyDrop::into_inner(this)) })
     }
+
+    /// Returns the inner value, if the `Arc` has exactly one strong reference.
+    ///
+    /// Otherwise, `None` is returned and the `Arc` is dropped.
+    ///
+    /// This is equivalent to `Arc::into_unique(this).map(UniqueArc::into_inner)`,
+    /// and thus has the same guarantee: if `Arc::into_inner` is called on every clone
+    /// of this `Arc`, exactly one of the calls returns the inner value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let x = Arc::new(3);
+    /// let y = Arc::clone(&x);
+    ///
+    /// assert_eq!(Arc::into_inner(x), None);
+    /// assert_eq!(Arc::into_inner(y), Some(3));
+    /// ```
+    pub fn into_inner(this: Self) -> Option<T> {
+        Self::into_unique(this).map(UniqueArc::into_inner)
+    }
+
+    /// Moves the inner value into a `Box`, if the `Arc` has exactly one strong reference.
+    ///
+    /// Otherwise, an [`Err`] is returned with the same [`Arc`] that was
+    /// passed in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let x = Arc::new(3);
+    /// assert_eq!(Arc::try_into_box(x), Ok(Box::new(3)));
+    ///
+    /// let x = Arc::new(4);
+    /// let _y = Arc::clone(&x);
+    /// assert_eq!(*Arc::try_into_box(x).unwrap_err(), 4);
+    /// ```
+    pub fn try_into_box(this: Self) -> Result<Box<T>, Self> {
+        Self::try_unwrap(this).map(Box::new)
+    }
 }
 
 impl<T> Arc<[T]> {
@@ -211,6 +403,106 @@ impl<T> Arc<[T]> {
     pub unsafe fn from_raw_slice(ptr: *const [T]) -> Self {
         Arc::from_raw(ptr)
     }
+
+    /// Moves the elements out into a `Vec`, if the `Arc` has exactly one strong reference.
+    ///
+    /// Otherwise, an [`Err`] is returned with the same [`Arc`] that was
+    /// passed in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let x: Arc<[u32]> = Arc::from(&[1, 2, 3][..]);
+    /// let y = Arc::clone(&x);
+    /// let x = Arc::try_into_vec(x).unwrap_err();
+    ///
+    /// drop(y);
+    /// assert_eq!(Arc::try_into_vec(x), Ok(vec![1, 2, 3]));
+    /// ```
+    pub fn try_into_vec(this: Self) -> Result<Vec<T>, Self> {
+        let unique = Self::try_unique(this)?;
+        let this = ManuallyDrop::new(unique.shareable());
+        let len = this.len();
+
+        let mut v = Vec::with_capacity(len);
+        unsafe {
+            // Safety:
+            // - the source is valid for reads for `len` elements, and we own them
+            // - `v` has capacity for `len` elements
+            // - they don't overlap (separate allocations)
+            ptr::copy_nonoverlapping(this.as_ptr() as *const T, v.as_mut_ptr(), len);
+            v.set_len(len);
+
+            // Free the allocation without dropping the elements, which were moved out.
+            // Safety: `[MaybeUninit<T>]` has the same layout as `[T]`, and we are the
+            //         only owner
+            let inner = this.ptr() as *mut ArcInner<[MaybeUninit<T>]>;
+            drop(Box::from_raw(inner));
+        }
+        Ok(v)
+    }
+
+    /// Moves the elements out into a `Box<[T]>`, if the `Arc` has exactly one strong
+    /// reference.
+    ///
+    /// Otherwise, an [`Err`] is returned with the same [`Arc`] that was
+    /// passed in.
+    ///
+    /// This is the slice counterpart of [`Arc::try_into_box`].
+    pub fn try_into_boxed_slice(this: Self) -> Result<Box<[T]>, Self> {
+        Self::try_into_vec(this).map(Vec::into_boxed_slice)
+    }
+}
+
+impl Arc<dyn Any> {
+    /// Attempts to downcast the `Arc<dyn Any>` to a concrete type.
+    ///
+    /// On failure, the original `Arc` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::any::Any;
+    /// use triomphe::Arc;
+    ///
+    /// let any: Arc<dyn Any> = unsafe { Arc::from_raw(Arc::into_raw(Arc::new(7u8)) as *const dyn Any) };
+    /// let any = any.downcast::<u32>().unwrap_err();
+    /// assert_eq!(*any.downcast::<u8>().unwrap(), 7);
+    /// ```
+    pub fn downcast<T: Any>(self) -> Result<Arc<T>, Self> {
+        if (*self).is::<T>() {
+            // Safety: the pointee is a `T`, so this only drops the vtable of the pointer.
+            //         The refcount is not modified.
+            unsafe {
+                Ok(Arc::from_raw_inner(
+                    Arc::into_raw_inner(self) as *mut ArcInner<T>
+                ))
+            }
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Arc<dyn Any + Send + Sync> {
+    /// Attempts to downcast the `Arc<dyn Any + Send + Sync>` to a concrete type.
+    ///
+    /// On failure, the original `Arc` is returned.
+    pub fn downcast<T: Any + Send + Sync>(self) -> Result<Arc<T>, Self> {
+        if (*self).is::<T>() {
+            // Safety: the pointee is a `T`, so this only drops the vtable of the pointer.
+            //         The refcount is not modified.
+            unsafe {
+                Ok(Arc::from_raw_inner(
+                    Arc::into_raw_inner(self) as *mut ArcInner<T>
+                ))
+            }
+        } else {
+            Err(self)
+        }
+    }
 }
 
 impl<T: ?Sized> Arc<T> {
@@ -225,6 +517,32 @@ impl<T: ?Sized> Arc<T> {
         this.as_ptr()
     }
 
+    /// Consumes the `Arc` and leaks it, returning a reference to the data
+    /// which lives for the rest of the program, as with [`Box::leak`].
+    ///
+    /// The reference count is never decremented, so neither the data nor the allocation
+    /// are ever freed. Other clones of this `Arc` remain valid, but also never free it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::OnceLock;
+    /// use triomphe::Arc;
+    ///
+    /// static INTERNED: OnceLock<&'static str> = OnceLock::new();
+    ///
+    /// let interned = INTERNED.get_or_init(|| Arc::leak(Arc::<str>::from("interned")));
+    /// assert_eq!(*interned, "interned");
+    /// ```
+    #[inline]
+    pub fn leak<'a>(this: Self) -> &'a T
+    where
+        T: 'a,
+    {
+        // Safety: the allocation is never freed since our reference is never released
+        unsafe { &*Arc::into_raw(this) }
+    }
+
     /// Reconstruct the `Arc<T>` from a raw pointer obtained from into_raw()
     ///
     /// Note: This raw pointer will be offset in the allocation and must be preceded
@@ -251,9 +569,49 @@ impl<T: ?Sized> Arc<T> {
         Arc::from_raw_inner(arc_inner_ptr as *mut ArcInner<T>)
     }
 
+    /// Increments the reference count of the `Arc<T>` associated with the
+    /// provided pointer by one, without reconstructing the `Arc`.
+    ///
+    /// # Safety
+    ///
+    /// - The given pointer must be a valid pointer to `T` that came from
+    ///   [`Arc::into_raw`] (or [`Arc::as_ptr`] on a live `Arc`).
+    /// - The associated `Arc` must be valid (i.e. its reference count must be
+    ///   at least 1) for the duration of this call.
+    #[inline]
+    pub unsafe fn increment_strong_count(ptr: *const T) {
+        // Retain an `Arc` without taking ownership of it, by wrapping it in `ManuallyDrop`
+        let arc = ManuallyDrop::new(Arc::from_raw(ptr));
+        // Now increase the refcount, but don't drop the new refcount either
+        let _arc_clone: ManuallyDrop<_> = arc.clone();
+    }
+
+    /// Decrements the reference count of the `Arc<T>` associated with the
+    /// provided pointer by one, dropping the data and freeing the allocation
+    /// if this was the last reference.
+    ///
+    /// # Safety
+    ///
+    /// - The given pointer must be a valid pointer to `T` that came from
+    ///   [`Arc::into_raw`] (or [`Arc::as_ptr`] on a live `Arc`).
+    /// - The associated `Arc` must be valid (i.e. its reference count must be
+    ///   at least 1) when this is called, and the reference being released
+    ///   must be owned by the caller: every call has to be balanced by a prior
+    ///   [`Arc::into_raw`] or [`Arc::increment_strong_count`].
+    /// - If the count drops to zero, the pointer must not be used afterwards.
+    #[inline]
+    pub unsafe fn decrement_strong_count(ptr: *const T) {
+        drop(Arc::from_raw(ptr));
+    }
+
     /// Returns the raw pointer.
     ///
     /// Same as into_raw except `self` isn't consumed.
+    ///
+    /// The pointer points at the `T` itself, not at the refcount in front of it, and
+    /// for unsized `T` (such as `Arc<[T]>`) it carries the same metadata as `&T`.
+    /// It is the same for all clones of this `Arc`, and stays valid for as long as
+    /// any of them is alive.
     #[inline]
     pub fn as_ptr(&self) -> *const T {
         // SAFETY: This cannot go through a reference to `data`, because this method
@@ -286,6 +644,11 @@ impl<T: ?Sized> Arc<T> {
     ///
     /// The function is called `strong_count` to mirror `std::sync::Arc::strong_count`,
     /// however `triomphe::Arc` does not support weak references.
+    ///
+    /// This is only a snapshot: other threads may clone or drop the `Arc` concurrently,
+    /// so the count may already be stale when it is returned. It is not synchronized
+    /// with those threads either; use [`Arc::is_unique`] to check whether the `Arc` is
+    /// uniquely owned.
     #[inline]
     pub fn strong_count(this: &Self) -> usize {
         this.inner().count.load(Relaxed)
@@ -302,9 +665,20 @@ impl<T: ?Sized> Arc<T> {
     /// The `ptr` must point to a valid instance, allocated by an `Arc`. The reference could will
     /// not be modified.
     pub(super) unsafe fn from_raw_inner(ptr: *mut ArcInner<T>) -> Self {
+        Arc::from_inner(ptr::NonNull::new_unchecked(ptr))
+    }
+
+    /// Construct an `Arc` from an allocated `ArcInner`.
+    /// # Safety
+    /// The `p` must point to a valid instance, allocated by an `Arc`, and the caller must
+    /// own one of its references.
+    #[inline]
+    pub(crate) unsafe fn from_inner(p: ptr::NonNull<ArcInner<T>>) -> Self {
         Arc {
-            p: ptr::NonNull::new_unchecked(ptr),
+            p,
             phantom: PhantomData,
+            #[cfg(feature = "allocator_api")]
+            alloc: Global,
         }
     }
 
@@ -319,6 +693,7 @@ impl<T: ?Sized> Arc<T> {
     }
 
     // Non-inlined part of `drop`. Just invokes the destructor.
+    #[cfg(not(feature = "allocator_api"))]
     #[inline(never)]
     unsafe fn drop_slow(&mut self) {
         let _ = Box::from_raw(self.ptr());
@@ -331,6 +706,16 @@ impl<T: ?Sized> Arc<T> {
         ptr::addr_eq(this.ptr(), other.ptr())
     }
 
+    /// Hashes the address of the allocation, consistently with [`Arc::ptr_eq`].
+    ///
+    /// This is useful to implement identity-based hashing in a `Hash` impl; see also
+    /// [`ByAddress`](crate::ByAddress). Like `ptr_eq`, this ignores the metadata of
+    /// `dyn Trait` pointers.
+    #[inline]
+    pub fn ptr_hash<H: Hasher>(this: &Self, state: &mut H) {
+        (this.ptr() as *const ()).hash(state)
+    }
+
     pub(crate) fn ptr(&self) -> *mut ArcInner<T> {
         self.p.as_ptr()
     }
@@ -353,11 +738,7 @@ impl<T: ?Sized> Arc<T> {
         value_layout: Layout,
         mem_to_arcinner: impl FnOnce(*mut u8) -> *mut ArcInner<T>,
     ) -> NonNull<ArcInner<T>> {
-        let layout = Layout::new::<ArcInner<()>>()
-            .extend(value_layout)
-            .unwrap()
-            .0
-            .pad_to_align();
+        let layout = layout_for_value_layout(value_layout);
 
         // Safety: we propagate safety requirements to the caller
         unsafe {
@@ -381,17 +762,13 @@ impl<T: ?Sized> Arc<T> {
     ///
     /// `value_layout` must be correct for `T`.
     #[allow(unused_unsafe)]
-    unsafe fn try_allocate_for_layout(
+    pub(super) unsafe fn try_allocate_for_layout(
         value_layout: Layout,
         mem_to_arcinner: impl FnOnce(*mut u8) -> *mut ArcInner<T>,
-    ) -> Result<NonNull<ArcInner<T>>, ()> {
-        let layout = Layout::new::<ArcInner<()>>()
-            .extend(value_layout)
-            .unwrap()
-            .0
-            .pad_to_align();
+    ) -> Result<NonNull<ArcInner<T>>, AllocError> {
+        let layout = try_layout_for_value_layout(value_layout).ok_or(AllocError)?;
 
-        let ptr = NonNull::new(alloc::alloc::alloc(layout)).ok_or(())?;
+        let ptr = NonNull::new(alloc::alloc::alloc(layout)).ok_or(AllocError)?;
 
         // Initialize the ArcInner
         let inner = mem_to_arcinner(ptr.as_ptr());
@@ -408,37 +785,127 @@ impl<T: ?Sized> Arc<T> {
 }
 
 impl<H, T> Arc<HeaderSlice<H, [T]>> {
+    /// The layout of a `HeaderSlice<H, [T]>` with a slice of `len` elements.
+    pub(super) fn header_and_slice_layout(len: usize) -> Layout {
+        header_slice_layout(Layout::new::<H>(), Layout::new::<T>(), len)
+    }
+
     pub(super) fn allocate_for_header_and_slice(
         len: usize,
     ) -> NonNull<ArcInner<HeaderSlice<H, [T]>>> {
-        let layout = Layout::new::<H>()
-            .extend(Layout::array::<T>(len).unwrap())
-            .unwrap()
-            .0
-            .pad_to_align();
+        Self::try_allocate_for_header_and_slice(len).unwrap_or_else(|_| {
+            handle_alloc_error(layout_for_value_layout(Self::header_and_slice_layout(len)))
+        })
+    }
 
-        unsafe {
-            // Safety:
-            // - the provided closure does not change the pointer (except for meta & type)
-            // - the provided layout is valid for `HeaderSlice<H, [T]>`
-            Arc::allocate_for_layout(layout, |mem| {
-                // Synthesize the fat pointer. We do this by claiming we have a direct
-                // pointer to a [T], and then changing the type of the borrow. The key
-                // point here is that the length portion of the fat pointer applies
-                // only to the number of elements in the dynamically-sized portion of
-                // the type, so the value will be the same whether it points to a [T]
-                // or something else with a [T] as its last member.
-                let fake_slice = ptr::slice_from_raw_parts_mut(mem as *mut T, len);
-                fake_slice as *mut ArcInner<HeaderSlice<H, [T]>>
-            })
-        }
+    #[allow(clippy::type_complexity)]
+    pub(super) fn try_allocate_for_header_and_slice(
+        len: usize,
+    ) -> Result<NonNull<ArcInner<HeaderSlice<H, [T]>>>, AllocError> {
+        let mem = alloc_header_slice(Layout::new::<H>(), Layout::new::<T>(), len)?;
+
+        // Synthesize the fat pointer. We do this by claiming we have a direct
+        // pointer to a [T], and then changing the type of the borrow. The key
+        // point here is that the length portion of the fat pointer applies
+        // only to the number of elements in the dynamically-sized portion of
+        // the type, so the value will be the same whether it points to a [T]
+        // or something else with a [T] as its last member.
+        let fake_slice = ptr::slice_from_raw_parts_mut(mem.as_ptr() as *mut T, len);
+        let inner = fake_slice as *mut ArcInner<HeaderSlice<H, [T]>>;
+        debug_assert_eq!(
+            unsafe { Layout::for_value(&*inner) },
+            layout_for_value_layout(Self::header_and_slice_layout(len))
+        );
+
+        // Safety: `inner` has the same address as `mem`, which is non-null
+        Ok(unsafe { NonNull::new_unchecked(inner) })
     }
 }
 
+/// The layout of a `HeaderSlice` whose header and elements have the given
+/// layouts, with `len` elements.
+fn header_slice_layout(header: Layout, element: Layout, len: usize) -> Layout {
+    try_header_slice_layout(header, element, len).expect("capacity overflow")
+}
+
+/// Like [`header_slice_layout`], but returns `None` if the size overflows, so
+/// that the fallible constructors can report it as an allocation failure.
+fn try_header_slice_layout(header: Layout, element: Layout, len: usize) -> Option<Layout> {
+    let size = element.size().checked_mul(len)?;
+    let slice = Layout::from_size_align(size, element.align()).ok()?;
+    let (layout, _) = header.extend(slice).ok()?;
+    Some(layout.pad_to_align())
+}
+
+/// Allocates an `ArcInner` for a `HeaderSlice` whose header and elements have
+/// the given layouts, with `len` elements, and initializes its refcount to 1.
+///
+/// This only depends on the layouts, so that the allocation code is shared by
+/// all the `from_header_and_*` constructors, instead of being duplicated for
+/// each of their (many) instantiations: only writing the header and elements
+/// is generic. Crates building header slices of many types, such as interners,
+/// should see a smaller `.text` as a result.
+#[inline(never)]
+fn alloc_header_slice(
+    header: Layout,
+    element: Layout,
+    len: usize,
+) -> Result<NonNull<u8>, AllocError> {
+    let layout = try_header_slice_layout(header, element, len)
+        .and_then(try_layout_for_value_layout)
+        .ok_or(AllocError)?;
+
+    // Safety: the layout is not zero-sized, since it includes the refcount
+    let mem = NonNull::new(unsafe { alloc::alloc::alloc(layout) }).ok_or(AllocError)?;
+
+    // Safety: `ArcInner` is `repr(C)`, so the allocation starts with the refcount
+    unsafe {
+        ptr::write(
+            mem.as_ptr() as *mut atomic::AtomicUsize,
+            atomic::AtomicUsize::new(1),
+        )
+    };
+
+    Ok(mem)
+}
+
 impl<T> Arc<MaybeUninit<T>> {
     /// Create an Arc contains an `MaybeUninit<T>`.
+    ///
+    /// The value is allocated directly on the heap, without going through the stack.
     pub fn new_uninit() -> Self {
-        Arc::new(MaybeUninit::<T>::uninit())
+        UniqueArc::new_uninit().shareable()
+    }
+
+    /// Create an Arc contains an `MaybeUninit<T>`, returning an error if the allocation fails.
+    pub fn try_new_uninit() -> Result<Self, AllocError> {
+        unsafe {
+            // Safety: the closure only changes the type of the pointer
+            Ok(Arc::from_inner(Self::try_allocate_for_layout(
+                Layout::new::<MaybeUninit<T>>(),
+                |mem| mem as *mut ArcInner<MaybeUninit<T>>,
+            )?))
+        }
+    }
+
+    /// Create an Arc contains an `MaybeUninit<T>` whose bytes are all zero.
+    ///
+    /// The memory is obtained zeroed from the allocator, see
+    /// [`MaybeUninit::zeroed`] for examples of correct and incorrect usage.
+    pub fn new_zeroed() -> Self {
+        unsafe {
+            let layout = Layout::new::<ArcInner<MaybeUninit<T>>>();
+            let ptr = alloc::alloc::alloc_zeroed(layout);
+            let p = NonNull::new(ptr)
+                .unwrap_or_else(|| handle_alloc_error(layout))
+                .cast::<ArcInner<MaybeUninit<T>>>();
+            ptr::write(
+                ptr::addr_of_mut!((*p.as_ptr()).count),
+                atomic::AtomicUsize::new(1),
+            );
+
+            Arc::from_inner(p)
+        }
     }
 
     /// Calls `MaybeUninit::write` on the value contained.
@@ -492,46 +959,115 @@ impl<T> Arc<[MaybeUninit<T>]> {
     pub unsafe fn assume_init(self) -> Arc<[T]> {
         Arc::from_raw_inner(ManuallyDrop::new(self).ptr() as _)
     }
+
+    /// Initializes the slice with the items of `items`, converting it into an `Arc<[T]>`.
+    ///
+    /// Only the first `len()` items are taken from the iterator. If it yields fewer
+    /// items, the ones written so far are dropped and the (still uninitialized) `Arc`
+    /// is returned. It is also returned untouched if it is not unique.
+    ///
+    /// ```
+    /// use std::mem::MaybeUninit;
+    /// use triomphe::Arc;
+    ///
+    /// let uninit: Arc<[MaybeUninit<u32>]> = Arc::new_uninit_slice(3);
+    /// let arc = uninit.try_init_from_iter(1..).unwrap();
+    /// assert_eq!(&*arc, [1, 2, 3]);
+    ///
+    /// let uninit: Arc<[MaybeUninit<u32>]> = Arc::new_uninit_slice(3);
+    /// assert!(uninit.try_init_from_iter(1..3).is_err());
+    /// ```
+    pub fn try_init_from_iter<I>(mut self, items: I) -> Result<Arc<[T]>, Self>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let slice = match Arc::get_mut(&mut self) {
+            Some(slice) => slice,
+            None => return Err(self),
+        };
+        let len = slice.len();
+
+        // Drops the items written so far if the iterator runs short, or panics
+        struct Guard<'a, T> {
+            slice: &'a mut [MaybeUninit<T>],
+            initialized: usize,
+        }
+
+        impl<'a, T> Drop for Guard<'a, T> {
+            fn drop(&mut self) {
+                for item in &mut self.slice[..self.initialized] {
+                    // Safety: the first `initialized` items were written
+                    unsafe { item.assume_init_drop() }
+                }
+            }
+        }
+
+        let mut guard = Guard {
+            slice,
+            initialized: 0,
+        };
+        for item in items.into_iter().take(len) {
+            guard.slice[guard.initialized].write(item);
+            guard.initialized += 1;
+        }
+
+        if guard.initialized < len {
+            drop(guard);
+            return Err(self);
+        }
+        mem::forget(guard);
+
+        // Safety: all `len` items were written
+        Ok(unsafe { self.assume_init() })
+    }
 }
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T: ?Sized> Clone for Arc<T> {
     #[inline]
     fn clone(&self) -> Self {
-        // Using a relaxed ordering is alright here, as knowledge of the
-        // original reference prevents other threads from erroneously deleting
-        // the object.
-        //
-        // As explained in the [Boost documentation][1], Increasing the
-        // reference counter can always be done with memory_order_relaxed: New
-        // references to an object can only be formed from an existing
-        // reference, and passing an existing reference from one thread to
-        // another must already provide any required synchronization.
-        //
-        // [1]: (www.boost.org/doc/libs/1_55_0/doc/html/atomic/usage_examples.html)
-        let old_size = self.inner().count.fetch_add(1, Relaxed);
-
-        // However we need to guard against massive refcounts in case someone
-        // is `mem::forget`ing Arcs. If we don't do this the count can overflow
-        // and users will use-after free. We racily saturate to `isize::MAX` on
-        // the assumption that there aren't ~2 billion threads incrementing
-        // the reference count at once. This branch will never be taken in
-        // any realistic program.
-        //
-        // We abort because such a program is incredibly degenerate, and we
-        // don't care to support it.
-        if old_size > MAX_REFCOUNT {
-            abort();
+        increment(&self.inner().count);
+        unsafe { Arc::from_inner(self.p) }
+    }
+
+    /// Makes `self` point to the same allocation as `source`, like `*self = source.clone()`.
+    ///
+    /// The data is never cloned in place, even if `self` is unique: both `Arc`s
+    /// share the allocation afterwards. If they already do, this does nothing.
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        if !Arc::ptr_eq(self, source) {
+            *self = source.clone();
         }
+    }
+}
 
-        unsafe {
-            Arc {
-                p: ptr::NonNull::new_unchecked(self.ptr()),
-                phantom: PhantomData,
-            }
+#[cfg(feature = "allocator_api")]
+impl<T: ?Sized, A: Allocator + Clone> Clone for Arc<T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        // Safety: while this arc is alive the inner pointer is valid
+        increment(unsafe { &self.p.as_ref().count });
+        Arc {
+            p: self.p,
+            phantom: PhantomData,
+            alloc: self.alloc.clone(),
+        }
+    }
+
+    /// Makes `self` point to the same allocation as `source`, like `*self = source.clone()`.
+    ///
+    /// The data is never cloned in place, even if `self` is unique: both `Arc`s
+    /// share the allocation afterwards. If they already do, this does nothing.
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        if !ptr::addr_eq(self.p.as_ptr(), source.p.as_ptr()) {
+            *self = source.clone();
         }
     }
 }
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T: ?Sized> Deref for Arc<T> {
     type Target = T;
 
@@ -541,6 +1077,17 @@ impl<T: ?Sized> Deref for Arc<T> {
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T: ?Sized, A: Allocator> Deref for Arc<T, A> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // Safety: while this arc is alive the inner pointer is valid
+        unsafe { &self.p.as_ref().data }
+    }
+}
+
 impl<T: Clone> Arc<T> {
     /// Makes a mutable reference to the `Arc`, cloning if necessary
     ///
@@ -604,6 +1151,11 @@ impl<T: Clone> Arc<T> {
 
 impl<T: ?Sized> Arc<T> {
     /// Provides mutable access to the contents _if_ the `Arc` is uniquely owned.
+    ///
+    /// All the owning handles to an allocation, i.e. `Arc`s but also the
+    /// [`OffsetArc`]s, [`ThinArc`](crate::ThinArc)s and so on converted from them,
+    /// share a single refcount: this returns `None` if any of them exists
+    /// besides `this`.
     #[inline]
     pub fn get_mut(this: &mut Self) -> Option<&mut T> {
         if this.is_unique() {
@@ -616,6 +1168,20 @@ impl<T: ?Sized> Arc<T> {
         }
     }
 
+    /// Provides mutable access to the contents without checking that the `Arc` is uniquely owned.
+    ///
+    /// See [`Arc::get_mut`] for a safe alternative.
+    ///
+    /// # Safety
+    ///
+    /// No other pointer to the same allocation may be used for the duration of the returned
+    /// borrow. This includes other `Arc`s, but also `ArcBorrow`s, `OffsetArc`s, `ThinArc`s,
+    /// raw pointers obtained from [`Arc::as_ptr`] and the like.
+    #[inline]
+    pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
+        &mut (*this.ptr()).data
+    }
+
     /// Provides unique access to the arc _if_ the `Arc` is uniquely owned.
     pub fn get_unique(this: &mut Self) -> Option<&mut UniqueArc<T>> {
         Self::try_as_unique(this).ok()
@@ -634,6 +1200,23 @@ impl<T: ?Sized> Arc<T> {
         this.inner().count.load(Acquire)
     }
 
+    /// Returns a wrapper whose `Debug` implementation shows the strong count
+    /// along with the value, as `Arc { strong: 2, value: .. }`.
+    ///
+    /// The `Debug` implementation of `Arc` itself only shows the value.
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let x = Arc::new(5);
+    /// let _y = x.clone();
+    /// assert_eq!(format!("{:?}", Arc::debug_with_count(&x)), "Arc { strong: 2, value: 5 }");
+    /// ```
+    #[inline]
+    pub fn debug_with_count(this: &Self) -> DebugWithCount<'_, T> {
+        DebugWithCount(this)
+    }
+
     /// Returns a [`UniqueArc`] if the [`Arc`] has exactly one strong reference.
     ///
     /// Otherwise, an [`Err`] is returned with the same [`Arc`] that was
@@ -674,42 +1257,15 @@ impl<T: ?Sized> Arc<T> {
         }
     }
 
+    #[cfg(not(feature = "allocator_api"))]
     fn drop_inner(&mut self) {
-        // Because `fetch_sub` is already atomic, we do not need to synchronize
-        // with other threads unless we are going to delete the object.
-        if self.inner().count.fetch_sub(1, Release) != 1 {
-            return;
-        }
-
-        // FIXME(bholley): Use the updated comment when [2] is merged.
-        //
-        // This load is needed to prevent reordering of use of the data and
-        // deletion of the data.  Because it is marked `Release`, the decreasing
-        // of the reference count synchronizes with this `Acquire` load. This
-        // means that use of the data happens before decreasing the reference
-        // count, which happens before this load, which happens before the
-        // deletion of the data.
-        //
-        // As explained in the [Boost documentation][1],
-        //
-        // > It is important to enforce any possible access to the object in one
-        // > thread (through an existing reference) to *happen before* deleting
-        // > the object in a different thread. This is achieved by a "release"
-        // > operation after dropping a reference (any access to the object
-        // > through this reference must obviously happened before), and an
-        // > "acquire" operation before deleting the object.
-        //
-        // [1]: (www.boost.org/doc/libs/1_55_0/doc/html/atomic/usage_examples.html)
-        // [2]: https://github.com/rust-lang/rust/pull/41714
-        self.inner().count.load(Acquire);
-
-        unsafe {
-            self.drop_slow();
+        if decrement(&self.inner().count) {
+            unsafe { self.drop_slow() }
         }
     }
 }
 
-#[cfg(not(feature = "unstable_dropck_eyepatch"))]
+#[cfg(not(any(feature = "unstable_dropck_eyepatch", feature = "allocator_api")))]
 impl<T: ?Sized> Drop for Arc<T> {
     #[inline]
     fn drop(&mut self) {
@@ -718,7 +1274,7 @@ impl<T: ?Sized> Drop for Arc<T> {
 }
 
 // SAFETY: We do not access the inner `T`, so we are fine to drop Arc with an already dropped T.
-#[cfg(feature = "unstable_dropck_eyepatch")]
+#[cfg(all(feature = "unstable_dropck_eyepatch", not(feature = "allocator_api")))]
 unsafe impl<#[may_dangle] T: ?Sized> Drop for Arc<T> {
     #[inline]
     fn drop(&mut self) {
@@ -726,18 +1282,131 @@ unsafe impl<#[may_dangle] T: ?Sized> Drop for Arc<T> {
     }
 }
 
-impl<T: ?Sized + PartialEq> PartialEq for Arc<T> {
-    fn eq(&self, other: &Arc<T>) -> bool {
-        // TODO: pointer equality is incorrect if `T` is not `Eq`.
-        Self::ptr_eq(self, other) || *(*self) == *(*other)
+#[cfg(all(feature = "allocator_api", not(feature = "unstable_dropck_eyepatch")))]
+impl<T: ?Sized, A: Allocator> Drop for Arc<T, A> {
+    #[inline]
+    fn drop(&mut self) {
+        self.drop_inner();
     }
+}
 
-    #[allow(clippy::partialeq_ne_impl)]
-    fn ne(&self, other: &Arc<T>) -> bool {
+// SAFETY: We do not access the inner `T`, so we are fine to drop Arc with an already dropped T.
+#[cfg(all(feature = "allocator_api", feature = "unstable_dropck_eyepatch"))]
+unsafe impl<#[may_dangle] T: ?Sized, A: Allocator> Drop for Arc<T, A> {
+    #[inline]
+    fn drop(&mut self) {
+        self.drop_inner();
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> Arc<T, A> {
+    /// Construct an `Arc<T, A>` in the provided allocator.
+    #[inline]
+    pub fn new_in(data: T, alloc: A) -> Self {
+        match Self::try_new_in(data, alloc) {
+            Ok(arc) => arc,
+            Err(_) => handle_alloc_error(Layout::new::<ArcInner<T>>()),
+        }
+    }
+
+    /// Construct an `Arc<T, A>` in the provided allocator, returning an error
+    /// if the allocation fails.
+    #[inline]
+    pub fn try_new_in(data: T, alloc: A) -> Result<Self, AllocError> {
+        let p = alloc
+            .allocate(Layout::new::<ArcInner<T>>())
+            .map_err(|_| AllocError)?
+            .cast::<ArcInner<T>>();
+
+        // Safety: `p` is valid for writes (just allocated)
+        unsafe {
+            ptr::write(
+                p.as_ptr(),
+                ArcInner {
+                    count: atomic::AtomicUsize::new(1),
+                    data,
+                },
+            )
+        };
+
+        Ok(Arc {
+            p,
+            phantom: PhantomData,
+            alloc,
+        })
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T: ?Sized, A: Allocator> Arc<T, A> {
+    /// Returns a reference to the allocator this `Arc` was allocated in.
+    #[inline]
+    pub fn allocator(this: &Self) -> &A {
+        &this.alloc
+    }
+
+    fn drop_inner(&mut self) {
+        // Safety: while this arc is alive the inner pointer is valid
+        if !decrement(unsafe { &self.p.as_ref().count }) {
+            return;
+        }
+
+        // Safety: this was the last reference, so we own the allocation. The
+        // layout is computed before dropping the data, and matches the one it
+        // was allocated with (through `Box` or `alloc` for the global allocator).
+        unsafe {
+            let layout = Layout::for_value(self.p.as_ref());
+            ptr::drop_in_place(ptr::addr_of_mut!((*self.p.as_ptr()).data));
+            self.alloc.deallocate(self.p.cast(), layout);
+        }
+    }
+}
+
+/// Compares the pointed-to values, short-circuiting if both `Arc`s share an allocation.
+///
+/// Like std's `Arc`, this only compares `Arc`s of the same type: neither `Arc<T> == T`
+/// nor `Arc<T> == Arc<U>` for `T: PartialEq<U>` is implemented. Either would make the
+/// right-hand side of comparisons such as `arc == x.into()` ambiguous, the latter as soon
+/// as another crate adds a `PartialEq` impl for `T` (e.g. `serde_json`'s `i32 == Value`).
+/// Compare the values instead, e.g. `*arc == 5`, or `*a == *b` for an `Arc<str>` and an
+/// `Arc<String>`.
+impl<T: ?Sized + PartialEq> PartialEq for Arc<T> {
+    fn eq(&self, other: &Arc<T>) -> bool {
+        // TODO: pointer equality is incorrect if `T` is not `Eq`.
+        Self::ptr_eq(self, other) || *(*self) == *(*other)
+    }
+
+    #[allow(clippy::partialeq_ne_impl)]
+    fn ne(&self, other: &Arc<T>) -> bool {
         !Self::ptr_eq(self, other) && *(*self) != *(*other)
     }
 }
 
+/// Compares the string with a string slice, so that `arc == "hello"` works.
+impl PartialEq<str> for Arc<str> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        **self == *other
+    }
+}
+
+/// Compares the string with a string slice, so that `arc == "hello"` works.
+impl<'a> PartialEq<&'a str> for Arc<str> {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        **self == **other
+    }
+}
+
+/// Compares the elements with those of a slice.
+impl<T: PartialEq> PartialEq<[T]> for Arc<[T]> {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
 impl<T: ?Sized + PartialOrd> PartialOrd for Arc<T> {
     fn partial_cmp(&self, other: &Arc<T>) -> Option<Ordering> {
         (**self).partial_cmp(&**other)
@@ -780,9 +1449,23 @@ impl<T: ?Sized + fmt::Debug> fmt::Debug for Arc<T> {
     }
 }
 
+/// Shows the strong count of an `Arc` along with its value, see [`Arc::debug_with_count`].
+pub struct DebugWithCount<'a, T: ?Sized>(&'a Arc<T>);
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for DebugWithCount<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Arc")
+            .field("strong", &Arc::count(self.0))
+            .field("value", &&**self.0)
+            .finish()
+    }
+}
+
+/// Prints the address of the data, as returned by [`Arc::as_ptr`]: clones print
+/// the same address, consistently with [`Arc::ptr_eq`].
 impl<T: ?Sized> fmt::Pointer for Arc<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Pointer::fmt(&self.ptr(), f)
+        fmt::Pointer::fmt(&self.as_ptr(), f)
     }
 }
 
@@ -793,6 +1476,8 @@ impl<T: Default> Default for Arc<T> {
     }
 }
 
+/// Hashes the pointed-to value, consistently with `PartialEq`. To hash by allocation
+/// instead, see [`ByAddress`](crate::ByAddress).
 impl<T: ?Sized + Hash> Hash for Arc<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         (**self).hash(state)
@@ -842,7 +1527,7 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Arc<T> {
 }
 
 #[cfg(feature = "serde")]
-impl<T: Serialize> Serialize for Arc<T> {
+impl<T: ?Sized + Serialize> Serialize for Arc<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ::serde::ser::Serializer,
@@ -884,6 +1569,13 @@ unsafe impl<T, U: ?Sized> unsize::CoerciblePtr<U> for Arc<T> {
     }
 }
 
+// Allows `Arc<T>` to coerce to `Arc<U>` wherever `T` unsizes to `U`, e.g. `Arc<String>` to `Arc<dyn Display>`.
+#[cfg(feature = "nightly")]
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<Arc<U>> for Arc<T> {}
+
+#[cfg(feature = "nightly")]
+impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<Arc<U>> for Arc<T> {}
+
 #[track_caller]
 fn must_be_unique<T: ?Sized>(arc: &mut Arc<T>) -> &mut UniqueArc<T> {
     match Arc::try_as_unique(arc) {
@@ -895,11 +1587,14 @@ fn must_be_unique<T: ?Sized>(arc: &mut Arc<T>) -> &mut UniqueArc<T> {
 #[cfg(test)]
 mod tests {
     use crate::arc::Arc;
+    use crate::test_drops::{Counted, DropCounter};
     use alloc::borrow::ToOwned;
-    use alloc::string::String;
+    use alloc::boxed::Box;
+    use alloc::string::{String, ToString};
     use alloc::vec::Vec;
     use core::iter::FromIterator;
     use core::mem::MaybeUninit;
+    use core::sync::atomic::Ordering::SeqCst;
     #[cfg(feature = "unsize")]
     use unsize::{CoerceUnsize, Coercion};
 
@@ -918,6 +1613,44 @@ mod tests {
         assert_eq!(Arc::try_unwrap(y), Ok(100));
     }
 
+    #[test]
+    fn try_unwrap_does_not_drop() {
+        let drops = DropCounter::new();
+        let x = Arc::new(drops.counted(()));
+        let y = x.clone();
+
+        let x = Arc::try_unwrap(x).unwrap_err();
+        assert!(Arc::ptr_eq(&x, &y));
+        assert_eq!(Arc::count(&x), 2);
+        drop(y);
+
+        let value = Arc::try_unwrap(x).unwrap();
+        assert_eq!(drops.drops(), 0);
+
+        drop(value);
+        assert_eq!(drops.drops(), 1);
+    }
+
+    #[test]
+    fn try_new() {
+        let x = Arc::try_new(String::from("hello")).unwrap();
+        assert_eq!(*x, "hello");
+        assert!(x.is_unique());
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", target_pointer_width = "64"))]
+    fn try_new_uninit_failure() {
+        use crate::test_allocator::FAILING_SIZE;
+        use crate::AllocError;
+
+        let x = Arc::<MaybeUninit<[u8; FAILING_SIZE]>>::try_new_uninit();
+        assert_eq!(x.err(), Some(AllocError));
+
+        let y = Arc::<MaybeUninit<[u8; 16]>>::try_new_uninit().unwrap();
+        assert!(y.is_unique());
+    }
+
     #[test]
     #[cfg(feature = "unsize")]
     fn coerce_to_slice() {
@@ -934,6 +1667,19 @@ mod tests {
         assert_eq!((*x)(), 42);
     }
 
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn coerce_unsized() {
+        use alloc::string::ToString;
+        use core::fmt::Display;
+
+        let x: Arc<dyn Display> = Arc::new(String::from("coerced"));
+        assert_eq!(x.to_string(), "coerced");
+
+        let y: Arc<[u8]> = Arc::new([1u8, 2, 3]);
+        assert_eq!(*y, [1, 2, 3]);
+    }
+
     #[test]
     #[allow(deprecated)]
     fn maybeuninit() {
@@ -945,7 +1691,31 @@ mod tests {
     }
 
     #[test]
-    #[allow(deprecated)]
+    fn maybeuninit_zeroed() {
+        let arc: Arc<MaybeUninit<[u8; 1024]>> = Arc::new_zeroed();
+        assert!(arc.is_unique());
+
+        let arc = unsafe { arc.assume_init() };
+        assert!(arc.iter().all(|&b| b == 0));
+        assert_eq!(arc.clone().len(), 1024);
+        assert!(arc.is_unique());
+    }
+
+    #[test]
+    fn maybeuninit_get_mut() {
+        struct Page([u8; 4096]);
+
+        let mut arc: Arc<MaybeUninit<Page>> = Arc::new_uninit();
+        let page = Arc::get_mut(&mut arc).unwrap().as_mut_ptr();
+        unsafe { core::ptr::addr_of_mut!((*page).0).write_bytes(7, 1) };
+
+        let arc = unsafe { arc.assume_init() };
+        assert!(arc.0.iter().all(|&b| b == 7));
+        assert!(arc.is_unique());
+    }
+
+    #[test]
+    #[allow(deprecated, clippy::borrow_deref_ref)]
     #[should_panic = "`Arc` must be unique in order for this operation to be safe"]
     fn maybeuninit_ub_to_proceed() {
         let mut uninit = Arc::new_uninit();
@@ -961,7 +1731,7 @@ mod tests {
     }
 
     #[test]
-    #[allow(deprecated)]
+    #[allow(deprecated, clippy::borrow_deref_ref)]
     #[should_panic = "`Arc` must be unique in order for this operation to be safe"]
     fn maybeuninit_slice_ub_to_proceed() {
         let mut uninit = Arc::new_uninit_slice(13);
@@ -976,6 +1746,44 @@ mod tests {
         let _read = &*x;
     }
 
+    #[test]
+    fn try_init_from_iter() {
+        let drops = DropCounter::new();
+        let counted = |n| (0..n).map(|i| drops.counted(i));
+
+        // Exact
+        let uninit = Arc::new_uninit_slice(3);
+        let arc = uninit.try_init_from_iter(counted(3)).unwrap();
+        assert_eq!(arc.iter().map(|x| x.0).collect::<Vec<_>>(), [0, 1, 2]);
+        drop(arc);
+        assert_eq!(drops.drops(), 3);
+
+        // Short: the written items are dropped, and the `Arc` is handed back
+        let uninit = Arc::new_uninit_slice(3);
+        let uninit = uninit.try_init_from_iter(counted(2)).unwrap_err();
+        assert_eq!(drops.drops(), 5);
+        assert_eq!(uninit.len(), 3);
+        assert!(uninit.is_unique());
+
+        // It can be filled again afterwards
+        let arc = uninit.try_init_from_iter(counted(3)).unwrap();
+        drop(arc);
+        assert_eq!(drops.drops(), 8);
+
+        // Over-long: the extra items are left in the iterator
+        let mut items = 0..5;
+        let uninit: Arc<[MaybeUninit<u32>]> = Arc::new_uninit_slice(3);
+        let arc = uninit.try_init_from_iter(&mut items).unwrap();
+        assert_eq!(&*arc, [0, 1, 2]);
+        assert_eq!(items.next(), Some(3));
+
+        // Shared
+        let uninit: Arc<[MaybeUninit<u32>]> = Arc::new_uninit_slice(3);
+        let clone = uninit.clone();
+        let uninit = uninit.try_init_from_iter(0..3).unwrap_err();
+        assert!(Arc::ptr_eq(&uninit, &clone));
+    }
+
     #[test]
     fn maybeuninit_array() {
         let mut arc: Arc<[MaybeUninit<_>]> = Arc::new_uninit_slice(5);
@@ -1008,6 +1816,414 @@ mod tests {
         assert_eq!(*arc, [0, 1, 2, 3, 4]);
     }
 
+    #[test]
+    fn maybeuninit_slice_get_mut() {
+        let mut arc: Arc<[MaybeUninit<String>]> = Arc::new_uninit_slice(3);
+        assert_eq!(arc.len(), 3);
+
+        let slice = Arc::get_mut(&mut arc).unwrap();
+        slice[0].write("0".to_owned());
+        slice[1].write("1".to_owned());
+        slice[2].write("2".to_owned());
+
+        let arc = unsafe { arc.assume_init() };
+        assert_eq!(*arc, ["0".to_owned(), "1".to_owned(), "2".to_owned()]);
+    }
+
+    #[test]
+    fn pin() {
+        use core::marker::PhantomPinned;
+
+        struct Pinned {
+            value: u32,
+            _pinned: PhantomPinned,
+        }
+
+        let pinned = Arc::pin(Pinned {
+            value: 42,
+            _pinned: PhantomPinned,
+        });
+        let clone = pinned.clone();
+        assert_eq!(pinned.value, 42);
+        assert!(core::ptr::eq(&*pinned, &*clone));
+    }
+
+    #[test]
+    fn get_mut_unchecked() {
+        let mut x = Arc::new(String::from("unique"));
+        unsafe { Arc::get_mut_unchecked(&mut x) }.push_str(" access");
+        assert_eq!(*x, "unique access");
+        assert!(x.is_unique());
+    }
+
+    #[test]
+    // Miri would report the leaked allocation
+    #[cfg_attr(miri, ignore)]
+    fn leak() {
+        let x = Arc::new(String::from("leaked"));
+        let y = x.clone();
+
+        let leaked: &'static String = Arc::leak(x);
+        assert_eq!(leaked, "leaked");
+        assert_eq!(Arc::count(&y), 2);
+
+        drop(y);
+        assert_eq!(leaked, "leaked");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    // Miri can't spawn the child process
+    #[cfg_attr(miri, ignore)]
+    fn refcount_overflow_aborts() {
+        use std::process::Command;
+
+        const CHILD_ENV: &str = "TRIOMPHE_REFCOUNT_OVERFLOW_CHILD";
+
+        if std::env::var_os(CHILD_ENV).is_some() {
+            // Forge the count that a `mem::forget` loop would eventually reach
+            let x = Arc::new(0u8);
+            x.inner().count.store(super::MAX_REFCOUNT + 1, SeqCst);
+            core::mem::forget(x.clone());
+            // Not reached if the overflow check aborts
+            std::process::exit(0);
+        }
+
+        // Aborting takes the whole test binary down, so re-run this test in a child process
+        let status = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "arc::tests::refcount_overflow_aborts",
+                "--test-threads=1",
+            ])
+            .env(CHILD_ENV, "1")
+            .status()
+            .unwrap();
+        assert!(!status.success());
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            assert_eq!(status.signal(), Some(6 /* SIGABRT */));
+        }
+    }
+
+    #[test]
+    fn ptr_eq_slices() {
+        let x: Arc<[u8]> = Arc::from(&[1u8, 2, 3][..]);
+        let y: Arc<[u8]> = Arc::from(&[1u8, 2, 3][..]);
+        // Same length metadata, distinct allocations
+        assert!(!Arc::ptr_eq(&x, &y));
+        assert!(Arc::ptr_eq(&x, &x.clone()));
+
+        let empty1: Arc<[u8]> = Arc::from(&[][..]);
+        let empty2: Arc<[u8]> = Arc::from(&[][..]);
+        assert!(!Arc::ptr_eq(&empty1, &empty2));
+    }
+
+    #[test]
+    fn as_ptr_points_at_data() {
+        let x = Arc::new(String::from("ptr"));
+        let y = x.clone();
+        assert_eq!(x.as_ptr(), y.as_ptr());
+        assert_eq!(x.as_ptr(), &*x as *const String);
+        assert_ne!(x.as_ptr() as *const core::ffi::c_void, x.heap_ptr());
+
+        drop(x);
+        assert_eq!(unsafe { &*y.as_ptr() }, "ptr");
+
+        let slice: Arc<[u16]> = Arc::from(&[1u16, 2, 3][..]);
+        let ptr = slice.clone().as_ptr();
+        assert_eq!(ptr, &*slice as *const [u16]);
+        assert_eq!(unsafe { &*ptr }, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn try_into_vec() {
+        let drops = DropCounter::new();
+        let x = Arc::from_iter_exact((0..3).map(|i| drops.counted(i)));
+        let y = x.clone();
+
+        // Shared: the same `Arc` is returned
+        let x = Arc::try_into_vec(x).unwrap_err();
+        assert!(Arc::ptr_eq(&x, &y));
+        drop(y);
+
+        // Unique: the elements are moved out, not dropped
+        let v = Arc::try_into_vec(x).unwrap();
+        assert_eq!(v.iter().map(|c| c.0).collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(drops.drops(), 0);
+        drop(v);
+        assert_eq!(drops.drops(), 3);
+
+        let empty: Arc<[String]> = Arc::from(Vec::new());
+        assert!(Arc::try_into_vec(empty).unwrap().is_empty());
+    }
+
+    #[test]
+    fn try_into_box() {
+        let drops = DropCounter::new();
+        let x = Arc::new(drops.counted(1));
+        let y = x.clone();
+        let x = Arc::try_into_box(x).unwrap_err();
+        assert!(Arc::ptr_eq(&x, &y));
+        drop(y);
+
+        let boxed: Box<Counted<i32>> = Arc::try_into_box(x).unwrap();
+        assert_eq!(boxed.0, 1);
+        assert_eq!(drops.drops(), 0);
+        drop(boxed);
+        assert_eq!(drops.drops(), 1);
+
+        let x = Arc::from_iter_exact((0..3).map(|i| drops.counted(i)));
+        let y = x.clone();
+        let x = Arc::try_into_boxed_slice(x).unwrap_err();
+        drop(y);
+
+        let boxed: Box<[Counted<i32>]> = Arc::try_into_boxed_slice(x).unwrap();
+        assert_eq!(boxed.len(), 3);
+        assert_eq!(boxed[2].0, 2);
+        assert_eq!(drops.drops(), 1);
+        drop(boxed);
+        assert_eq!(drops.drops(), 4);
+    }
+
+    #[test]
+    fn clone_from() {
+        let source = Arc::from_header_and_slice(1u8, &[1u32, 2, 3]);
+        let mut unique = Arc::from_header_and_slice(2u8, &[4u32, 5, 6]);
+
+        unique.clone_from(&source);
+        assert!(Arc::ptr_eq(&unique, &source));
+        assert_eq!(unique.header, 1);
+        assert_eq!(Arc::count(&source), 2);
+
+        // Already sharing the allocation: the count is unchanged
+        unique.clone_from(&source);
+        assert_eq!(Arc::count(&source), 2);
+
+        drop(unique);
+        assert!(source.is_unique());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn hash_by_contents() {
+        use core::hash::BuildHasher;
+        use std::collections::hash_map::RandomState;
+        use std::collections::HashSet;
+
+        let x: Arc<[u8]> = Arc::from(&[1u8, 2, 3][..]);
+        let y: Arc<[u8]> = Arc::from(&[1u8, 2, 3][..]);
+        assert!(!Arc::ptr_eq(&x, &y));
+
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(&x), state.hash_one(&y));
+        assert_eq!(state.hash_one(&x), state.hash_one(&[1u8, 2, 3][..]));
+
+        let set: HashSet<Arc<[u8]>> = [x, y].iter().cloned().collect();
+        assert_eq!(set.len(), 1);
+
+        let a = Arc::from_header_and_slice(1u8, &[1u32, 2]);
+        let b = Arc::from_header_and_slice(1u8, &[1u32, 2]);
+        assert_eq!(state.hash_one(&a), state.hash_one(&b));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn debug_with_count() {
+        use alloc::format;
+
+        let x = Arc::new(alloc::vec![1u8]);
+        let _y = x.clone();
+        let _z = x.clone();
+
+        assert_eq!(format!("{:?}", x), "[1]");
+        assert_eq!(
+            format!("{:?}", Arc::debug_with_count(&x)),
+            "Arc { strong: 3, value: [1] }"
+        );
+
+        let pretty = format!("{:#?}", Arc::debug_with_count(&x));
+        assert!(pretty.contains("strong: 3,"));
+        assert!(pretty.starts_with("Arc {\n"));
+    }
+
+    #[test]
+    fn increment_decrement_strong_count() {
+        let drops = DropCounter::new();
+        let ptr = Arc::into_raw(Arc::new(drops.counted(())));
+        unsafe {
+            Arc::increment_strong_count(ptr);
+            assert_eq!(
+                Arc::count(&*core::mem::ManuallyDrop::new(Arc::from_raw(ptr))),
+                2
+            );
+
+            Arc::decrement_strong_count(ptr);
+            assert_eq!(drops.drops(), 0);
+            Arc::decrement_strong_count(ptr);
+        }
+        assert_eq!(drops.drops(), 1);
+    }
+
+    #[test]
+    fn eq_with_value() {
+        let s: Arc<str> = Arc::from("hello");
+        assert!(s == *"hello");
+        assert!(s == "hello");
+        assert!(s != "world");
+
+        let bytes: Arc<[u8]> = Arc::from(&b"bytes"[..]);
+        assert!(bytes == b"bytes"[..]);
+        assert!(bytes != b"other"[..]);
+
+        // Other values are compared through a dereference
+        let x = Arc::new(5);
+        assert!(*x == 5);
+        assert!(x == Arc::new(5));
+    }
+
+    #[test]
+    fn eq_infers_the_other_arc() {
+        let x: Arc<i32> = Arc::new(5);
+        assert!(x == 5.into());
+        assert!(x != Default::default());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn ptr_hash() {
+        use std::collections::hash_map::RandomState;
+        use std::hash::{BuildHasher, Hasher};
+
+        let state = RandomState::new();
+        let hash = |arc: &Arc<String>| {
+            let mut hasher = state.build_hasher();
+            Arc::ptr_hash(arc, &mut hasher);
+            hasher.finish()
+        };
+
+        let a = Arc::new(String::from("same"));
+        let b = Arc::new(String::from("same"));
+        assert_eq!(hash(&a), hash(&a.clone()));
+        assert_ne!(hash(&a), hash(&b));
+        assert_eq!(state.hash_one(&*a), state.hash_one(&*b));
+    }
+
+    #[test]
+    fn eq_by_contents() {
+        let a: Arc<[u8]> = Arc::from(&b"bytes"[..]);
+        let b: Arc<[u8]> = Arc::from(&b"bytes"[..]);
+        assert!(!Arc::ptr_eq(&a, &b));
+        assert_eq!(a, b);
+        assert_ne!(a, Arc::from(&b"other"[..]));
+
+        // Different types compare through their values
+        let s: Arc<str> = Arc::from("hello");
+        let string = Arc::new(String::from("hello"));
+        assert!(*s == *string);
+    }
+
+    #[test]
+    fn try_unique_mutate_and_share() {
+        let arc = Arc::new(String::from("unique"));
+        let mut unique = Arc::try_unique(arc).unwrap();
+        unique.push_str(" again");
+        let arc = unique.shareable();
+        assert_eq!(*arc, "unique again");
+
+        let clone = arc.clone();
+        let arc = match Arc::try_unique(arc) {
+            Ok(_) => panic!("a shared `Arc` is not unique"),
+            Err(arc) => arc,
+        };
+        // The same `Arc` is given back, and the refcount is unchanged
+        assert!(Arc::ptr_eq(&arc, &clone));
+        assert_eq!(Arc::count(&arc), 2);
+
+        drop(clone);
+        let mut unique = Arc::try_unique(arc).unwrap();
+        unique.clear();
+        assert_eq!(crate::UniqueArc::into_inner(unique), "");
+    }
+
+    #[test]
+    fn header_slice_layouts() {
+        use crate::HeaderSlice;
+        use alloc::alloc::Layout;
+
+        fn check<H: Default, T: Default>(len: usize) {
+            let arc = Arc::from_header_and_iter(H::default(), (0..len).map(|_| T::default()));
+            assert_eq!(arc.slice.len(), len);
+            assert_eq!(
+                Layout::for_value(&*arc),
+                Arc::<HeaderSlice<H, [T]>>::header_and_slice_layout(len)
+            );
+            assert_eq!(arc.slice.as_ptr() as usize % core::mem::align_of::<T>(), 0);
+        }
+
+        for len in [0, 1, 3, 16].iter().copied() {
+            check::<(), u8>(len);
+            check::<u8, u64>(len);
+            check::<u64, u8>(len);
+            check::<[u8; 3], u16>(len);
+            check::<u32, ()>(len);
+            check::<String, String>(len);
+        }
+    }
+
+    #[test]
+    fn get_mut_counts_every_handle() {
+        use crate::ThinArc;
+
+        let mut arc = Arc::new(String::from("a"));
+        let offset = Arc::into_raw_offset(arc.clone());
+        assert!(Arc::get_mut(&mut arc).is_none());
+        let offset_clone = offset.clone();
+        drop(offset);
+        assert!(Arc::get_mut(&mut arc).is_none());
+        let projected = Arc::project(Arc::from_raw_offset(offset_clone), |s| s.as_str());
+        assert!(Arc::get_mut(&mut arc).is_none());
+        drop(projected);
+        Arc::get_mut(&mut arc).unwrap().push('b');
+
+        let mut arc = Arc::from_header_with_length_and_slice((), &[1u8, 2]);
+        let thin = Arc::into_thin(arc.clone());
+        assert!(Arc::get_mut(&mut arc).is_none());
+        let thin_clone = ThinArc::clone(&thin);
+        drop(thin);
+        assert!(Arc::get_mut(&mut arc).is_none());
+        drop(thin_clone);
+        Arc::get_mut(&mut arc).unwrap().slice[0] = 3;
+        assert_eq!(arc.slice, [3, 2]);
+    }
+
+    #[test]
+    fn pointer_fmt() {
+        use alloc::format;
+
+        let a = Arc::new(1u32);
+        let b = Arc::new(1u32);
+        assert_eq!(format!("{:p}", a), format!("{:p}", a.clone()));
+        assert_eq!(format!("{:p}", a), format!("{:p}", Arc::as_ptr(&a)));
+        assert_ne!(format!("{:p}", a), format!("{:p}", b));
+    }
+
+    #[test]
+    fn eq_short_circuits_on_same_allocation() {
+        struct NeverEq;
+        impl PartialEq for NeverEq {
+            fn eq(&self, _: &Self) -> bool {
+                panic!("compared by value");
+            }
+        }
+
+        let a = Arc::new(NeverEq);
+        let clone = a.clone();
+        assert!(a == clone);
+        assert!(!(a != clone));
+    }
+
     #[test]
     fn roundtrip() {
         let arc: Arc<usize> = Arc::new(0usize);
@@ -1037,6 +2253,23 @@ mod tests {
         assert_eq!(["ololo".to_owned(), "trololo".to_owned()], *arc);
     }
 
+    #[test]
+    fn collect_exact_size() {
+        let arc: Arc<[String]> = (0..3).map(|i| i.to_string()).collect();
+        assert!(arc.is_unique());
+        assert_eq!(["0".to_owned(), "1".to_owned(), "2".to_owned()], *arc);
+
+        let empty: Arc<[String]> = core::iter::empty().collect();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn collect_unknown_size() {
+        let arc: Arc<[u32]> = (0..10).filter(|i| i % 3 == 0).collect();
+        assert!(arc.is_unique());
+        assert_eq!([0, 3, 6, 9], *arc);
+    }
+
     #[test]
     fn roundtrip_slice() {
         let arc = Arc::from(Vec::from_iter([17, 19]));
@@ -1128,7 +2361,11 @@ mod tests {
         assert_eq!(1, Arc::strong_count(&arc));
         let arc2 = arc.clone();
         assert_eq!(2, Arc::strong_count(&arc));
+        let arc3 = arc.clone();
+        assert_eq!(3, Arc::strong_count(&arc));
+        assert_eq!(Arc::count(&arc), Arc::strong_count(&arc3));
         drop(arc);
+        drop(arc3);
         assert_eq!(1, Arc::strong_count(&arc2));
     }
 
@@ -1160,6 +2397,24 @@ mod tests {
         assert_eq!(19, arc.get_me_an_integer());
     }
 
+    #[test]
+    fn downcast() {
+        use core::any::Any;
+
+        let x = Arc::new(String::from("plugin"));
+        let y = x.clone();
+        let any: Arc<dyn Any + Send + Sync> =
+            unsafe { Arc::from_raw(Arc::into_raw(x) as *const (dyn Any + Send + Sync)) };
+
+        let any = any.downcast::<u32>().unwrap_err();
+        assert_eq!(Arc::count(&y), 2);
+
+        let x = any.downcast::<String>().unwrap();
+        assert!(Arc::ptr_eq(&x, &y));
+        assert_eq!(Arc::count(&y), 2);
+        assert_eq!(*x, "plugin");
+    }
+
     #[test]
     fn into_unique() {
         let arc = Arc::new(42);
@@ -1177,6 +2432,191 @@ mod tests {
         assert_eq!(42, *arc_unique);
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn into_inner_race() {
+        for _ in 0..100 {
+            let x = Arc::new(String::from("race"));
+            let y = Arc::clone(&x);
+
+            let x_thread = std::thread::spawn(move || Arc::into_inner(x));
+            let y_thread = std::thread::spawn(move || Arc::into_inner(y));
+
+            let results = [x_thread.join().unwrap(), y_thread.join().unwrap()];
+            assert_eq!(results.iter().filter(|r| r.is_some()).count(), 1);
+        }
+    }
+
+    /// A bump allocator over a fixed buffer, counting deallocations.
+    #[cfg(feature = "allocator_api")]
+    struct Bump {
+        buf: core::cell::UnsafeCell<[MaybeUninit<u8>; 256]>,
+        used: core::cell::Cell<usize>,
+        frees: core::cell::Cell<usize>,
+    }
+
+    #[cfg(feature = "allocator_api")]
+    impl Bump {
+        fn new() -> Self {
+            Bump {
+                buf: core::cell::UnsafeCell::new([MaybeUninit::uninit(); 256]),
+                used: core::cell::Cell::new(0),
+                frees: core::cell::Cell::new(0),
+            }
+        }
+    }
+
+    #[cfg(feature = "allocator_api")]
+    unsafe impl alloc::alloc::Allocator for &Bump {
+        fn allocate(
+            &self,
+            layout: core::alloc::Layout,
+        ) -> Result<core::ptr::NonNull<[u8]>, alloc::alloc::AllocError> {
+            let base = self.buf.get() as *mut u8;
+            let start = self.used.get()
+                + base
+                    .wrapping_add(self.used.get())
+                    .align_offset(layout.align());
+            if start + layout.size() > 256 {
+                return Err(alloc::alloc::AllocError);
+            }
+            self.used.set(start + layout.size());
+
+            let p = unsafe { base.add(start) };
+            let slice = core::ptr::slice_from_raw_parts_mut(p, layout.size());
+            Ok(core::ptr::NonNull::new(slice).unwrap())
+        }
+
+        unsafe fn deallocate(&self, _: core::ptr::NonNull<u8>, _: core::alloc::Layout) {
+            self.frees.set(self.frees.get() + 1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn new_in_bump_allocator() {
+        let bump = Bump::new();
+        let x = Arc::new_in(String::from("bump"), &bump);
+        let y = x.clone();
+        assert_eq!(*y, "bump");
+        assert!(core::ptr::eq(*Arc::allocator(&x), &bump));
+
+        let buf = bump.buf.get() as *const u8;
+        let data = &*x as *const String as *const u8;
+        assert!(buf <= data && data < buf.wrapping_add(256));
+
+        drop(x);
+        assert_eq!(bump.frees.get(), 0);
+        drop(y);
+        assert_eq!(bump.frees.get(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn try_new_in_exhausted() {
+        let bump = Bump::new();
+        assert!(Arc::try_new_in([0u8; 200], &bump).is_ok());
+        assert_eq!(
+            Arc::try_new_in([0u8; 200], &bump).err(),
+            Some(super::AllocError)
+        );
+        assert_eq!(bump.frees.get(), 1);
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn loom_clone_drop_race() {
+        use loom::cell::UnsafeCell;
+
+        struct Payload {
+            value: UnsafeCell<usize>,
+        }
+        impl Drop for Payload {
+            fn drop(&mut self) {
+                // Races with the other thread's write unless the orderings of
+                // `Clone` and `Drop` make it happen before.
+                self.value.with(|v| assert!(unsafe { *v } <= 1));
+            }
+        }
+
+        loom::model(|| {
+            let drops = DropCounter::new();
+            let x = Arc::new(drops.counted(Payload {
+                value: UnsafeCell::new(0),
+            }));
+            let y = x.clone();
+
+            let thread = loom::thread::spawn(move || {
+                let z = y.clone();
+                // Safety: only this thread accesses the value while the other holds `x`
+                z.0.value.with_mut(|v| unsafe { *v = 1 });
+                drop(y);
+                drop(z);
+            });
+
+            let w = x.clone();
+            drop(x);
+            drop(w);
+            thread.join().unwrap();
+
+            assert_eq!(drops.drops(), 1);
+        });
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn loom_into_unique_race() {
+        use loom::cell::UnsafeCell;
+
+        loom::model(|| {
+            let x = Arc::new(UnsafeCell::new(0usize));
+            let y = x.clone();
+
+            let thread = loom::thread::spawn(move || {
+                // Safety: the other thread doesn't access the value until it gets
+                // unique access to it, after we gave up `y`
+                y.with_mut(|v| unsafe { *v = 1 });
+                Arc::into_unique(y).map(|y| y.with(|v| unsafe { *v }))
+            });
+            // Races with the other thread's write unless `into_unique` orders it before
+            let from_x = Arc::into_unique(x).map(|x| x.with(|v| unsafe { *v }));
+            let from_y = thread.join().unwrap();
+
+            assert_eq!(from_x.or(from_y), Some(1));
+        });
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn loom_try_unwrap_race() {
+        loom::model(|| {
+            let x = Arc::new(String::from("race"));
+            let y = x.clone();
+
+            let thread = loom::thread::spawn(move || Arc::try_unwrap(y).ok());
+            let from_x = Arc::try_unwrap(x).ok();
+            let from_y = thread.join().unwrap();
+
+            // Both can fail, but they can't both succeed
+            assert!(from_x.is_none() || from_y.is_none());
+        });
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn loom_into_inner_race() {
+        loom::model(|| {
+            let x = Arc::new(String::from("race"));
+            let y = x.clone();
+
+            let thread = loom::thread::spawn(move || Arc::into_inner(y));
+            let from_x = Arc::into_inner(x);
+            let from_y = thread.join().unwrap();
+
+            assert_eq!(from_x.is_some() as u8 + from_y.is_some() as u8, 1);
+        });
+    }
+
     #[allow(dead_code)]
     const fn is_partial_ord<T: ?Sized + PartialOrd>() {}
 
@@ -21,6 +21,11 @@ use super::Arc;
 ///
 /// `ArcBorrow` lets us deal with borrows of known-refcounted objects
 /// without needing to worry about where the `Arc<T>` is.
+///
+/// An `ArcBorrow` cannot be projected to a field of `T`: the refcount is found at a fixed
+/// offset before the pointee, which only holds for a pointer to the whole `T`. To borrow a
+/// field for the full lifetime `'a`, project the reference returned by [`ArcBorrow::get`]
+/// instead, and keep the `ArcBorrow` around where a new `Arc` may need to be created.
 #[derive(Debug, Eq, PartialEq)]
 #[repr(transparent)]
 pub struct ArcBorrow<'a, T: ?Sized + 'a>(pub(crate) NonNull<T>, pub(crate) PhantomData<&'a T>);
@@ -138,6 +143,35 @@ unsafe impl<'lt, T: 'lt, U: ?Sized + 'lt> unsize::CoerciblePtr<U> for ArcBorrow<
     }
 }
 
+#[test]
+fn with_arc_borrow() {
+    let x = Arc::new(42);
+    let y = x.clone();
+    let b = x.borrow_arc();
+
+    let (count, same) = b.with_arc(|arc| (Arc::strong_count(arc), Arc::ptr_eq(arc, &y)));
+    assert_eq!(count, 2);
+    assert!(same);
+    assert_eq!(Arc::strong_count(&x), 2);
+}
+
+#[test]
+fn project_arc_borrow() {
+    struct Big {
+        _a: u64,
+        b: u32,
+    }
+
+    fn field<'a>(borrow: ArcBorrow<'a, Big>) -> &'a u32 {
+        &borrow.get().b
+    }
+
+    let x = Arc::new(Big { _a: 1, b: 2 });
+    let b = field(x.borrow_arc());
+    assert_eq!(*b, 2);
+    assert_eq!(Arc::count(&x), 1);
+}
+
 #[test]
 fn clone_arc_borrow() {
     let x = Arc::new(42);
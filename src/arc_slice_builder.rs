@@ -0,0 +1,305 @@
+use alloc::alloc::{alloc, dealloc, handle_alloc_error, realloc, Layout};
+use core::cmp;
+use core::fmt;
+use core::iter::{Extend, IntoIterator};
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop};
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+
+use crate::atomic::AtomicUsize;
+use crate::{layout_for_value_layout, Arc, ArcInner, HeaderSlice};
+
+/// Builds an `Arc<[T]>` incrementally, like a `Vec<T>`, directly in the
+/// allocation of the `Arc`.
+///
+/// The elements are pushed into a buffer laid out as the final `Arc<[T]>`, with
+/// room for the refcount before them. [`finish`](ArcSliceBuilder::finish) thus
+/// doesn't copy the elements: it at most shrinks the buffer to fit, which the
+/// allocator can usually do in place. Unlike collecting into a `Vec` and then
+/// converting it, the final `Arc<[T]>` reuses the buffer's allocation.
+///
+/// ```
+/// use triomphe::{Arc, ArcSliceBuilder};
+///
+/// let mut builder = ArcSliceBuilder::new();
+/// builder.push(1);
+/// builder.extend([2, 3, 4].iter().copied());
+/// let arc: Arc<[i32]> = builder.finish();
+/// assert_eq!(*arc, [1, 2, 3, 4]);
+/// ```
+pub struct ArcSliceBuilder<T> {
+    // An `ArcInner<HeaderSlice<(), [T]>>` with room for `cap` elements, the first
+    // `len` of which are initialized. Its refcount is only written by `finish`.
+    //
+    // This is `None` until an element is pushed, and always for zero-sized `T`.
+    mem: Option<NonNull<u8>>,
+    cap: usize,
+    len: usize,
+    phantom: PhantomData<T>,
+}
+
+// Like a `Vec<T>`.
+unsafe impl<T: Send> Send for ArcSliceBuilder<T> {}
+unsafe impl<T: Sync> Sync for ArcSliceBuilder<T> {}
+
+impl<T> ArcSliceBuilder<T> {
+    /// Creates an empty builder. This doesn't allocate until an element is pushed.
+    #[inline]
+    pub fn new() -> Self {
+        ArcSliceBuilder {
+            mem: None,
+            cap: if mem::size_of::<T>() == 0 {
+                usize::MAX
+            } else {
+                0
+            },
+            len: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Creates an empty builder with room for at least `capacity` elements.
+    ///
+    /// If exactly `capacity` elements are pushed,
+    /// [`finish`](ArcSliceBuilder::finish) doesn't need to reallocate.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut builder = ArcSliceBuilder::new();
+        if capacity > builder.cap {
+            builder.grow_to(capacity);
+        }
+        builder
+    }
+
+    /// The number of elements the builder can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Reserves room for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        if required > self.cap {
+            self.grow_to(cmp::max(cmp::max(required, self.cap.saturating_mul(2)), 4));
+        }
+    }
+
+    /// Appends an element.
+    #[inline]
+    pub fn push(&mut self, item: T) {
+        if self.len == self.cap {
+            self.reserve(1);
+        }
+
+        // Safety: there is room for at least one more element
+        unsafe { self.as_mut_ptr().add(self.len).write(item) };
+        self.len += 1;
+    }
+
+    /// Turns the builder into an `Arc<[T]>` holding the pushed elements.
+    ///
+    /// If the capacity is larger than the number of elements, the buffer is
+    /// shrunk to fit; the elements are not copied by this, unless the
+    /// allocator needs to move them.
+    pub fn finish(self) -> Arc<[T]> {
+        let this = ManuallyDrop::new(self);
+        let layout = Self::layout(this.len);
+
+        let mem = match this.mem {
+            Some(mem) if this.cap == this.len => mem,
+            // Safety: `mem` was allocated with the layout for `cap` elements,
+            // which has the same alignment
+            Some(mem) => unsafe {
+                let mem = realloc(mem.as_ptr(), Self::layout(this.cap), layout.size());
+                NonNull::new(mem).unwrap_or_else(|| handle_alloc_error(layout))
+            },
+            // Nothing was pushed, or `T` is zero-sized: only the refcount needs memory
+            //
+            // Safety: the layout is not zero-sized, since it includes the refcount
+            None => unsafe {
+                NonNull::new(alloc(layout)).unwrap_or_else(|| handle_alloc_error(layout))
+            },
+        };
+
+        unsafe {
+            // Safety: `ArcInner` is `repr(C)`, so the allocation starts with the refcount
+            ptr::write(mem.as_ptr() as *mut AtomicUsize, AtomicUsize::new(1));
+
+            // Safety: the allocation is laid out as an `ArcInner<HeaderSlice<(), [T]>>`
+            // with `len` elements, all initialized, and the refcount is set
+            let inner = Self::inner(mem, this.len);
+            Arc::from_inner(NonNull::new_unchecked(inner)).into()
+        }
+    }
+
+    /// The layout of the allocation for `cap` elements.
+    fn layout(cap: usize) -> Layout {
+        layout_for_value_layout(Arc::<HeaderSlice<(), [T]>>::header_and_slice_layout(cap))
+    }
+
+    /// Synthesizes the fat pointer to the `ArcInner`, see `try_allocate_for_header_and_slice`.
+    fn inner(mem: NonNull<u8>, len: usize) -> *mut ArcInner<HeaderSlice<(), [T]>> {
+        ptr::slice_from_raw_parts_mut(mem.as_ptr() as *mut T, len) as *mut _
+    }
+
+    /// Reallocates the buffer to hold `new_cap` elements.
+    fn grow_to(&mut self, new_cap: usize) {
+        debug_assert!(mem::size_of::<T>() != 0 && new_cap > self.cap);
+
+        let layout = Self::layout(new_cap);
+        let mem = match self.mem {
+            // Safety: `mem` was allocated with the layout for `cap` elements,
+            // which has the same alignment
+            Some(mem) => unsafe { realloc(mem.as_ptr(), Self::layout(self.cap), layout.size()) },
+            // Safety: the layout is not zero-sized, since it includes the refcount
+            None => unsafe { alloc(layout) },
+        };
+
+        self.mem = Some(NonNull::new(mem).unwrap_or_else(|| handle_alloc_error(layout)));
+        self.cap = new_cap;
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        match self.mem {
+            // Safety: `mem` is a valid allocation for the `ArcInner`
+            Some(mem) => unsafe {
+                ptr::addr_of_mut!((*Self::inner(mem, self.cap)).data.slice) as *mut T
+            },
+            None => NonNull::dangling().as_ptr(),
+        }
+    }
+}
+
+impl<T> Drop for ArcSliceBuilder<T> {
+    fn drop(&mut self) {
+        unsafe {
+            // Safety: the first `len` elements are initialized
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.as_mut_ptr(), self.len));
+
+            if let Some(mem) = self.mem {
+                // Safety: `mem` was allocated with this layout
+                dealloc(mem.as_ptr(), Self::layout(self.cap));
+            }
+        }
+    }
+}
+
+impl<T> Default for ArcSliceBuilder<T> {
+    #[inline]
+    fn default() -> Self {
+        ArcSliceBuilder::new()
+    }
+}
+
+impl<T> Deref for ArcSliceBuilder<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        let data = match self.mem {
+            // Safety: `mem` is a valid allocation for the `ArcInner`
+            Some(mem) => unsafe {
+                ptr::addr_of!((*Self::inner(mem, self.cap)).data.slice) as *const T
+            },
+            None => NonNull::dangling().as_ptr(),
+        };
+        // Safety: the first `len` elements are initialized
+        unsafe { &*ptr::slice_from_raw_parts(data, self.len) }
+    }
+}
+
+impl<T> DerefMut for ArcSliceBuilder<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        let len = self.len;
+        // Safety: the first `len` elements are initialized
+        unsafe { &mut *ptr::slice_from_raw_parts_mut(self.as_mut_ptr(), len) }
+    }
+}
+
+impl<T> Extend<T> for ArcSliceBuilder<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        let items = items.into_iter();
+        self.reserve(items.size_hint().0);
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArcSliceBuilder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArcSliceBuilder;
+    use crate::Arc;
+    use alloc::string::{String, ToString};
+
+    #[test]
+    fn build_incrementally() {
+        let mut builder = ArcSliceBuilder::new();
+        assert!(builder.is_empty());
+        for i in 0..10 {
+            builder.push(i.to_string());
+        }
+        builder.extend((10..20).map(|i| i.to_string()));
+        builder[0].push('!');
+        assert_eq!(builder.len(), 20);
+
+        let arc: Arc<[String]> = builder.finish();
+        assert_eq!(arc.len(), 20);
+        assert_eq!(arc[0], "0!");
+        assert!(arc[1..]
+            .iter()
+            .enumerate()
+            .all(|(i, s)| *s == (i + 1).to_string()));
+        assert!(arc.is_unique());
+        assert_eq!(*arc.clone(), *arc);
+    }
+
+    #[test]
+    fn finish_reuses_allocation() {
+        let mut builder = ArcSliceBuilder::with_capacity(4);
+        assert_eq!(builder.capacity(), 4);
+        builder.extend([1u64, 2, 3, 4].iter().copied());
+        assert_eq!(builder.capacity(), 4);
+        let data = builder.as_ptr();
+
+        let arc = builder.finish();
+        assert_eq!((*arc).as_ptr(), data);
+        assert_eq!(*arc, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn finish_empty_and_zst() {
+        let arc: Arc<[String]> = ArcSliceBuilder::new().finish();
+        assert!(arc.is_empty());
+
+        // Some capacity was allocated, but nothing pushed
+        let arc: Arc<[String]> = ArcSliceBuilder::with_capacity(8).finish();
+        assert!(arc.is_empty());
+
+        let mut builder = ArcSliceBuilder::new();
+        assert_eq!(builder.capacity(), usize::MAX);
+        builder.extend(core::iter::repeat(()).take(1000));
+        let arc = builder.finish();
+        assert_eq!(arc.len(), 1000);
+    }
+
+    #[test]
+    fn unfinished_builder_drops_elements() {
+        let counter = crate::test_drops::DropCounter::new();
+        let mut builder = ArcSliceBuilder::new();
+        for _ in 0..5 {
+            builder.push(counter.counted(()));
+        }
+        drop(builder);
+        assert_eq!(counter.live(), 0);
+    }
+}
@@ -40,3 +40,32 @@ unsafe impl<T> RefCnt for Arc<T> {
         Arc::from_raw(ptr)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::ThinArc;
+    use arc_swap::ArcSwapAny;
+
+    #[test]
+    fn thin_arc_swap() {
+        let swap: ArcSwapAny<_> =
+            ArcSwapAny::new(ThinArc::from_header_and_slice(1u8, &[1u32, 2, 3]));
+        assert_eq!(*swap.load().header(), 1);
+        assert_eq!(swap.load().slice(), &[1, 2, 3]);
+
+        let new = ThinArc::from_header_and_slice(2u8, &[4u32, 5]);
+        let old = swap.swap(new.clone());
+        assert_eq!(*old.header(), 1);
+        assert_eq!(old.slice(), &[1, 2, 3]);
+        assert_eq!(ThinArc::strong_count(&old), 1);
+
+        let loaded = swap.load_full();
+        assert!(ThinArc::ptr_eq(&loaded, &new));
+        assert_eq!(*loaded.header(), 2);
+        assert_eq!(loaded.slice(), &[4, 5]);
+
+        drop(swap);
+        drop(loaded);
+        assert_eq!(ThinArc::strong_count(&new), 1);
+    }
+}
@@ -62,7 +62,7 @@ impl<A, B> ArcUnion<A, B> {
     }
 
     /// Returns an enum representing a borrow of either A or B.
-    pub fn borrow(&self) -> ArcUnionBorrow<A, B> {
+    pub fn borrow(&self) -> ArcUnionBorrow<'_, A, B> {
         if self.is_first() {
             let ptr = self.p.as_ptr() as *const A;
             let borrow = unsafe { ArcBorrow::from_ptr(ptr) };
@@ -77,13 +77,19 @@ impl<A, B> ArcUnion<A, B> {
     /// Creates an `ArcUnion` from an instance of the first type.
     #[inline]
     pub fn from_first(other: Arc<A>) -> Self {
-        unsafe { Self::new(Arc::into_raw(other) as *mut _) }
+        let ptr = Arc::into_raw(other);
+        // The data follows the refcount, so it is at least as aligned as `AtomicUsize`
+        debug_assert_eq!(ptr as usize & 0x1, 0, "the tag bit must be free");
+        unsafe { Self::new(ptr as *mut _) }
     }
 
     /// Creates an `ArcUnion` from an instance of the second type.
     #[inline]
     pub fn from_second(other: Arc<B>) -> Self {
-        unsafe { Self::new(((Arc::into_raw(other) as usize) | 0x1) as *mut _) }
+        let ptr = Arc::into_raw(other);
+        // The data follows the refcount, so it is at least as aligned as `AtomicUsize`
+        debug_assert_eq!(ptr as usize & 0x1, 0, "the tag bit must be free");
+        unsafe { Self::new(((ptr as usize) | 0x1) as *mut _) }
     }
 
     /// Returns true if this `ArcUnion` contains the first type.
@@ -99,7 +105,7 @@ impl<A, B> ArcUnion<A, B> {
     }
 
     /// Returns a borrow of the first type if applicable, otherwise `None`.
-    pub fn as_first(&self) -> Option<ArcBorrow<A>> {
+    pub fn as_first(&self) -> Option<ArcBorrow<'_, A>> {
         match self.borrow() {
             ArcUnionBorrow::First(x) => Some(x),
             ArcUnionBorrow::Second(_) => None,
@@ -107,7 +113,7 @@ impl<A, B> ArcUnion<A, B> {
     }
 
     /// Returns a borrow of the second type if applicable, otherwise None.
-    pub fn as_second(&self) -> Option<ArcBorrow<B>> {
+    pub fn as_second(&self) -> Option<ArcBorrow<'_, B>> {
         match self.borrow() {
             ArcUnionBorrow::First(_) => None,
             ArcUnionBorrow::Second(x) => Some(x),
@@ -126,12 +132,14 @@ impl<A, B> Clone for ArcUnion<A, B> {
 
 impl<A, B> Drop for ArcUnion<A, B> {
     fn drop(&mut self) {
+        // Go through the raw pointers: one derived from a `&A` or `&B` would
+        // only be valid for the data, not for the refcount before it.
         match self.borrow() {
             ArcUnionBorrow::First(x) => unsafe {
-                let _ = Arc::from_raw(&*x);
+                let _ = Arc::from_raw(x.0.as_ptr());
             },
             ArcUnionBorrow::Second(x) => unsafe {
-                let _ = Arc::from_raw(&*x);
+                let _ = Arc::from_raw(x.0.as_ptr());
             },
         }
     }
@@ -160,3 +168,83 @@ impl<'a, A, B> ArcUnionBorrow<'a, A, B> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, ArcUnion, ArcUnionBorrow};
+    use alloc::string::String;
+
+    #[test]
+    fn union_predicates_and_borrow() {
+        let first: ArcUnion<u32, String> = ArcUnion::from_first(Arc::new(7));
+        assert!(first.is_first());
+        assert!(!first.is_second());
+        match first.borrow() {
+            ArcUnionBorrow::First(x) => assert_eq!(*x, 7),
+            ArcUnionBorrow::Second(_) => panic!("expected the first variant"),
+        }
+        assert_eq!(first.as_first().map(|x| *x), Some(7));
+        assert!(first.as_second().is_none());
+
+        let second: ArcUnion<u32, String> = ArcUnion::from_second(Arc::new(String::from("b")));
+        assert!(second.is_second());
+        assert!(!second.is_first());
+        match second.borrow() {
+            ArcUnionBorrow::First(_) => panic!("expected the second variant"),
+            ArcUnionBorrow::Second(x) => assert_eq!(*x, "b"),
+        }
+        assert!(second.as_first().is_none());
+        assert_eq!(second.as_second().as_deref().map(String::as_str), Some("b"));
+    }
+
+    #[test]
+    fn union_borrow_does_not_clone() {
+        let arc = Arc::new(String::from("b"));
+        let union: ArcUnion<u32, String> = ArcUnion::from_second(arc.clone());
+        assert_eq!(Arc::count(&arc), 2);
+
+        let borrow = union.as_second().unwrap();
+        assert_eq!(Arc::count(&arc), 2);
+        assert!(core::ptr::eq(&*borrow, &*arc));
+        assert_eq!(ArcUnion::strong_count(&union), 2);
+    }
+
+    #[test]
+    fn union_drops_the_right_side() {
+        use core::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+        static FIRST_DROPS: AtomicUsize = AtomicUsize::new(0);
+        static SECOND_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        // One byte, so the tag bit relies on the refcount's alignment
+        struct First(#[allow(dead_code)] u8);
+        impl Drop for First {
+            fn drop(&mut self) {
+                FIRST_DROPS.fetch_add(1, SeqCst);
+            }
+        }
+
+        struct Second;
+        impl Drop for Second {
+            fn drop(&mut self) {
+                SECOND_DROPS.fetch_add(1, SeqCst);
+            }
+        }
+
+        let first: ArcUnion<First, Second> = ArcUnion::from_first(Arc::new(First(1)));
+        let second: ArcUnion<First, Second> = ArcUnion::from_second(Arc::new(Second));
+        assert!(first.is_first());
+        assert!(second.is_second());
+
+        let first2 = first.clone();
+        drop(first);
+        assert_eq!(FIRST_DROPS.load(SeqCst), 0);
+        drop(first2);
+        assert_eq!(FIRST_DROPS.load(SeqCst), 1);
+        assert_eq!(SECOND_DROPS.load(SeqCst), 0);
+
+        drop(second);
+        assert_eq!(FIRST_DROPS.load(SeqCst), 1);
+        assert_eq!(SECOND_DROPS.load(SeqCst), 1);
+    }
+}
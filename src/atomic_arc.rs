@@ -0,0 +1,246 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ptr::NonNull;
+
+use crate::atomic::AtomicPtr;
+use crate::atomic::Ordering::{Acquire, Relaxed, Release};
+use crate::hint;
+use crate::{Arc, ArcInner};
+
+/// The bit of the pointer set while a thread is accessing it.
+///
+/// `ArcInner` starts with its refcount, so it is always aligned enough for this
+/// bit to be free.
+const LOCKED: usize = 0x1;
+
+/// An `Arc` that can be loaded and replaced atomically, for example to share a
+/// configuration that is reloaded concurrently.
+///
+/// Loading briefly takes a lock, stored in the lowest bit of the pointer: this
+/// prevents a concurrent [`store`](AtomicArc::store) from freeing the allocation
+/// between reading the pointer and incrementing its refcount. The lock is only
+/// held for a few instructions, and never while running user code, but all
+/// operations taking it ([`load`](AtomicArc::load), [`store`](AtomicArc::store)
+/// and [`swap`](AtomicArc::swap)) spin while another thread holds it: they are
+/// not lock-free.
+///
+/// ```
+/// use triomphe::{Arc, AtomicArc};
+///
+/// let config = AtomicArc::new(Arc::new(1));
+/// let old = config.swap(Arc::new(2));
+/// assert_eq!(*old, 1);
+/// assert_eq!(*config.load(), 2);
+/// ```
+pub struct AtomicArc<T> {
+    p: AtomicPtr<ArcInner<T>>,
+    phantom: PhantomData<Arc<T>>,
+}
+
+unsafe impl<T: Sync + Send> Send for AtomicArc<T> {}
+unsafe impl<T: Sync + Send> Sync for AtomicArc<T> {}
+
+impl<T> AtomicArc<T> {
+    /// Creates a new `AtomicArc` holding `arc`.
+    #[inline]
+    pub fn new(arc: Arc<T>) -> Self {
+        AtomicArc {
+            p: AtomicPtr::new(Arc::into_raw_inner(arc)),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a new reference to the current `Arc`.
+    #[inline]
+    pub fn load(&self) -> Arc<T> {
+        let p = self.lock();
+
+        // Safety: while locked, the `Arc` held by `self` can't be replaced, and thus
+        // can't be dropped, so the allocation is alive
+        let arc = ManuallyDrop::new(unsafe { Arc::from_inner(p) });
+        let new = Arc::clone(&arc);
+
+        self.unlock(p);
+        new
+    }
+
+    /// Replaces the current `Arc` with `arc`, dropping the previous one.
+    #[inline]
+    pub fn store(&self, arc: Arc<T>) {
+        drop(self.swap(arc));
+    }
+
+    /// Replaces the current `Arc` with `arc`, returning the previous one.
+    #[inline]
+    pub fn swap(&self, arc: Arc<T>) -> Arc<T> {
+        let new = Arc::into_raw_inner(arc);
+
+        let old = self.lock();
+        // Safety: `into_raw_inner` never returns null
+        self.unlock(unsafe { NonNull::new_unchecked(new) });
+
+        // Safety: `self` owned a reference to `old`, which is now transferred to us
+        unsafe { Arc::from_inner(old) }
+    }
+
+    /// Consumes the `AtomicArc`, returning the current `Arc`.
+    #[inline]
+    pub fn into_inner(self) -> Arc<T> {
+        let this = ManuallyDrop::new(self);
+        // Safety: we own `self`, so it can't be locked, and its reference is
+        // transferred to the returned `Arc`
+        unsafe { Arc::from_raw_inner(this.p.load(Acquire)) }
+    }
+
+    /// Sets the lock bit, returning the (untagged) pointer.
+    #[inline]
+    fn lock(&self) -> NonNull<ArcInner<T>> {
+        loop {
+            let p = self.p.load(Relaxed);
+            if p as usize & LOCKED == 0 {
+                let locked = (p as usize | LOCKED) as *mut ArcInner<T>;
+                // Acquire synchronizes with the `Release` in `unlock`, so that
+                // we see the refcount and data of an `Arc` stored by another thread
+                //
+                // On success, the returned pointer has the same address as `p`, but
+                // not necessarily the same provenance: `p`'s allocation may have been
+                // freed and another one stored at the same address since we loaded it.
+                // Only the returned pointer, read by the exchange, is valid to use.
+                if let Ok(p) = self.p.compare_exchange_weak(p, locked, Acquire, Relaxed) {
+                    // Safety: the pointer always comes from `Arc::into_raw_inner`
+                    return unsafe { NonNull::new_unchecked(p) };
+                }
+            }
+            hint::spin_loop();
+        }
+    }
+
+    /// Clears the lock bit, storing `p`.
+    #[inline]
+    fn unlock(&self, p: NonNull<ArcInner<T>>) {
+        debug_assert_eq!(p.as_ptr() as usize & LOCKED, 0);
+        self.p.store(p.as_ptr(), Release);
+    }
+}
+
+impl<T> Drop for AtomicArc<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // Safety: we own `self`, so it can't be locked, and we own its reference
+        unsafe { drop(Arc::from_raw_inner(self.p.load(Acquire))) }
+    }
+}
+
+impl<T> From<Arc<T>> for AtomicArc<T> {
+    #[inline]
+    fn from(arc: Arc<T>) -> Self {
+        AtomicArc::new(arc)
+    }
+}
+
+impl<T: Default> Default for AtomicArc<T> {
+    #[inline]
+    fn default() -> Self {
+        AtomicArc::new(Arc::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for AtomicArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("AtomicArc").field(&self.load()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AtomicArc;
+    use crate::test_drops::DropCounter;
+    use crate::Arc;
+    use alloc::string::String;
+
+    #[test]
+    fn load_store_swap() {
+        let a = Arc::new(String::from("a"));
+        let atomic = AtomicArc::new(a.clone());
+        assert_eq!(Arc::count(&a), 2);
+
+        let loaded = atomic.load();
+        assert!(Arc::ptr_eq(&loaded, &a));
+        assert_eq!(Arc::count(&a), 3);
+
+        let old = atomic.swap(Arc::new(String::from("b")));
+        assert!(Arc::ptr_eq(&old, &a));
+        assert_eq!(*atomic.load(), "b");
+
+        atomic.store(Arc::new(String::from("c")));
+        assert_eq!(*atomic.load(), "c");
+        assert_eq!(*atomic.into_inner(), "c");
+
+        drop(old);
+        drop(loaded);
+        assert!(a.is_unique());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn concurrent_load_store() {
+        const STORES: usize = 1000;
+        let counter = DropCounter::new();
+        let config = |n: usize| Arc::new(counter.counted(n));
+        let atomic = Arc::new(AtomicArc::new(config(0)));
+
+        let readers: alloc::vec::Vec<_> = (0..4)
+            .map(|_| {
+                let atomic = atomic.clone();
+                std::thread::spawn(move || {
+                    let mut last = 0;
+                    while last < STORES {
+                        let config = atomic.load();
+                        // Each store is newer than the previous ones
+                        assert!(config.0 >= last);
+                        last = config.0;
+                    }
+                })
+            })
+            .collect();
+
+        for n in 1..=STORES {
+            if n % 2 == 0 {
+                atomic.store(config(n));
+            } else {
+                drop(atomic.swap(config(n)));
+            }
+        }
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        assert_eq!(counter.live(), 1);
+        drop(atomic);
+        assert_eq!(counter.live(), 0);
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn loom_load_store_race() {
+        loom::model(|| {
+            let drops = DropCounter::new();
+            let atomic = loom::sync::Arc::new(AtomicArc::new(Arc::new(drops.counted(0))));
+
+            let writer = {
+                let atomic = atomic.clone();
+                let value = drops.counted(1);
+                loom::thread::spawn(move || atomic.store(Arc::new(value)))
+            };
+
+            let loaded = atomic.load();
+            assert!(loaded.0 <= 1);
+            drop(loaded);
+
+            writer.join().unwrap();
+            assert_eq!(atomic.load().0, 1);
+            assert_eq!(drops.drops(), 1);
+        });
+    }
+}
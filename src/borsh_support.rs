@@ -0,0 +1,187 @@
+use alloc::vec::Vec;
+use borsh::error::ERROR_ZST_FORBIDDEN;
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use core::cmp;
+use core::mem::size_of;
+
+use crate::{Arc, HeaderSlice, HeaderWithLength, ThinArc};
+
+impl<T: ?Sized + BorshSerialize> BorshSerialize for Arc<T> {
+    #[inline]
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        (**self).serialize(writer)
+    }
+}
+
+impl<T: BorshDeserialize> BorshDeserialize for Arc<T> {
+    #[inline]
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        T::deserialize_reader(reader).map(Arc::new)
+    }
+}
+
+/// Deserialized in the same format as `Vec<T>`.
+impl<T: BorshDeserialize> BorshDeserialize for Arc<[T]> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let len = u32::deserialize_reader(reader)? as usize;
+        let arc = deserialize_slice(reader, (), len)?;
+        Ok(arc.into())
+    }
+}
+
+/// Serialized in the same format as `(H, Vec<T>)`.
+impl<H: BorshSerialize, T: BorshSerialize> BorshSerialize for ThinArc<H, T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.header().serialize(writer)?;
+        self.slice().serialize(writer)
+    }
+}
+
+/// Deserialized in the same format as `(H, Vec<T>)`.
+impl<H: BorshDeserialize, T: BorshDeserialize> BorshDeserialize for ThinArc<H, T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let header = H::deserialize_reader(reader)?;
+        let len = u32::deserialize_reader(reader)? as usize;
+        let arc = deserialize_slice(
+            reader,
+            HeaderWithLength {
+                header,
+                length: len,
+            },
+            len,
+        )?;
+        Ok(Arc::into_thin(arc))
+    }
+}
+
+/// Reads `len` elements after `header`.
+///
+/// Small slices are read directly into the `Arc`'s allocation. Larger ones are
+/// first read into a `Vec` growing as the elements are read, since `len` comes
+/// from the input and can't be trusted to allocate up front.
+fn deserialize_slice<H, T, R>(
+    reader: &mut R,
+    header: H,
+    len: usize,
+) -> Result<Arc<HeaderSlice<H, [T]>>>
+where
+    T: BorshDeserialize,
+    R: Read,
+{
+    forbid_zst::<T>()?;
+
+    let capacity = cautious_capacity::<T>(len);
+    if len <= capacity {
+        return Arc::try_from_header_and_fn(header, len, || T::deserialize_reader(reader))
+            .map_err(|_| Error::new(ErrorKind::OutOfMemory, "memory allocation failed"))?;
+    }
+
+    let mut items = Vec::with_capacity(capacity);
+    for _ in 0..len {
+        items.push(T::deserialize_reader(reader)?);
+    }
+    Ok(Arc::from_header_and_vec(header, items))
+}
+
+/// Like borsh's `Vec<T>` implementation, rejects zero-sized `T`s so that a
+/// small input can't make us loop for a long time.
+fn forbid_zst<T>() -> Result<()> {
+    if size_of::<T>() == 0 {
+        return Err(Error::new(ErrorKind::InvalidData, ERROR_ZST_FORBIDDEN));
+    }
+    Ok(())
+}
+
+/// The number of elements to allocate room for before reading a slice of `len`
+/// elements: like borsh's `Vec<T>` implementation, at most 4 KiB worth of them.
+fn cautious_capacity<T>(len: usize) -> usize {
+    cmp::min(len, 4096 / size_of::<T>())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, ThinArc};
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn borsh_arc() {
+        let arc = Arc::new(String::from("hello"));
+        let bytes = borsh::to_vec(&arc).unwrap();
+        assert_eq!(bytes, borsh::to_vec(&String::from("hello")).unwrap());
+
+        let arc: Arc<String> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(*arc, "hello");
+    }
+
+    #[test]
+    fn borsh_arc_slice() {
+        let v = vec![String::from("a"), String::from("bc")];
+        let arc: Arc<[String]> = v.clone().into();
+        let bytes = borsh::to_vec(&arc).unwrap();
+        assert_eq!(bytes, borsh::to_vec(&v).unwrap());
+
+        let arc: Arc<[String]> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(&*arc, &*v);
+
+        let empty: Arc<[u32]> =
+            borsh::from_slice(&borsh::to_vec(&Vec::<u32>::new()).unwrap()).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn borsh_thin_arc() {
+        let thin = ThinArc::from_header_and_slice(7u16, &[1u32, 2, 3]);
+        let bytes = borsh::to_vec(&thin).unwrap();
+        assert_eq!(bytes, borsh::to_vec(&(7u16, vec![1u32, 2, 3])).unwrap());
+
+        let thin: ThinArc<u16, u32> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(*thin.header(), 7);
+        assert_eq!(thin.slice(), &[1, 2, 3]);
+        assert_eq!(thin.len(), 3);
+    }
+
+    #[test]
+    fn borsh_truncated_input() {
+        let bytes = borsh::to_vec(&vec![String::from("a"), String::from("b")]).unwrap();
+        assert!(borsh::from_slice::<Arc<[String]>>(&bytes[..bytes.len() - 1]).is_err());
+        assert!(borsh::from_slice::<ThinArc<u8, u8>>(&[0, 2, 0, 0, 0, 1]).is_err());
+    }
+
+    #[test]
+    fn borsh_huge_length_prefix() {
+        use borsh::io::ErrorKind;
+
+        // Allocating room for `u32::MAX` such elements up front would fail with
+        // `OutOfMemory`, rather than running out of input after the first one.
+        let mut bytes = vec![0xff; 4];
+        bytes.extend_from_slice(&[0; 4096]);
+        let err = borsh::from_slice::<Arc<[[u8; 4096]]>>(&bytes).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        bytes.insert(0, 7);
+        let err = borsh::from_slice::<ThinArc<u8, [u8; 4096]>>(&bytes).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn borsh_large_slice() {
+        let v: Vec<u32> = (0..5000).collect();
+        let bytes = borsh::to_vec(&v).unwrap();
+
+        let arc: Arc<[u32]> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(&*arc, &*v);
+
+        let bytes = borsh::to_vec(&(3u8, &v)).unwrap();
+        let thin: ThinArc<u8, u32> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(*thin.header(), 3);
+        assert_eq!(thin.slice(), &*v);
+    }
+
+    #[test]
+    fn borsh_zst_forbidden() {
+        assert!(borsh::from_slice::<Arc<[()]>>(&[0xff, 0xff, 0xff, 0xff]).is_err());
+    }
+}
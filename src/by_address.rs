@@ -0,0 +1,109 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::Arc;
+
+/// A wrapper comparing and hashing an `Arc` by the address of its allocation,
+/// rather than by value.
+///
+/// This is useful to keep `Arc`s in a `HashSet` or `HashMap` with identity
+/// semantics: two value-equal `Arc`s are only considered equal if they share
+/// the same allocation, as with [`Arc::ptr_eq`].
+///
+/// ```
+/// use std::collections::HashSet;
+/// use triomphe::{Arc, ByAddress};
+///
+/// let a = Arc::new(1);
+/// let b = Arc::new(1);
+///
+/// let mut set = HashSet::new();
+/// set.insert(ByAddress(a.clone()));
+/// set.insert(ByAddress(b));
+/// assert_eq!(set.len(), 2);
+/// assert!(set.contains(&ByAddress(a)));
+/// ```
+#[derive(Clone)]
+pub struct ByAddress<A>(pub A);
+
+impl<T: ?Sized> ByAddress<Arc<T>> {
+    /// Unwraps the inner `Arc`.
+    #[inline]
+    pub fn into_inner(this: Self) -> Arc<T> {
+        this.0
+    }
+}
+
+impl<T: ?Sized> From<Arc<T>> for ByAddress<Arc<T>> {
+    #[inline]
+    fn from(arc: Arc<T>) -> Self {
+        ByAddress(arc)
+    }
+}
+
+impl<T: ?Sized> Deref for ByAddress<Arc<T>> {
+    type Target = Arc<T>;
+
+    #[inline]
+    fn deref(&self) -> &Arc<T> {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> PartialEq for ByAddress<Arc<T>> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: ?Sized> Eq for ByAddress<Arc<T>> {}
+
+impl<T: ?Sized> Hash for ByAddress<Arc<T>> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::ptr_hash(&self.0, state)
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for ByAddress<Arc<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::ByAddress;
+    use crate::Arc;
+    use alloc::string::String;
+    use std::collections::HashSet;
+
+    #[test]
+    fn distinct_equal_arcs_are_kept() {
+        let a = Arc::new(String::from("same"));
+        let b = Arc::new(String::from("same"));
+        assert_eq!(a, b);
+
+        let mut set = HashSet::new();
+        assert!(set.insert(ByAddress(a.clone())));
+        assert!(set.insert(ByAddress(b.clone())));
+        assert!(!set.insert(ByAddress(a.clone())));
+        assert_eq!(set.len(), 2);
+
+        assert!(set.contains(&ByAddress(b)));
+        assert!(!set.contains(&ByAddress(Arc::new(String::from("same")))));
+    }
+
+    #[test]
+    fn deref_to_arc() {
+        let a = ByAddress::from(Arc::new(String::from("a")));
+        assert_eq!(Arc::count(&a), 1);
+        assert_eq!(**a, "a");
+        assert_ne!(a, ByAddress(Arc::new(String::from("a"))));
+
+        let arc = ByAddress::into_inner(a);
+        assert_eq!(*arc, "a");
+    }
+}
@@ -0,0 +1,103 @@
+use bytemuck::{Pod, PodCastError};
+use core::mem::{align_of, size_of};
+use core::ptr;
+
+use crate::{Arc, ArcInner};
+
+impl Arc<[u8]> {
+    /// Reinterprets the bytes of this `Arc` as a slice of `T`, without copying them.
+    ///
+    /// This fails, returning the original `Arc`, if the bytes are not suitably aligned
+    /// for `T` or if their length is not a multiple of `size_of::<T>()`.
+    ///
+    /// The bytes of an `Arc` are always aligned to `align_of::<usize>()`, so casting to a
+    /// type with a stricter alignment (e.g. `u128` on some platforms) always fails.
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let bytes: Arc<[u8]> = Arc::from(&[1u8, 0, 0, 0, 2, 0, 0, 0][..]);
+    /// let ints = Arc::try_cast_slice::<u32>(bytes).unwrap();
+    /// assert_eq!(&*ints, &[u32::from_le_bytes([1, 0, 0, 0]), u32::from_le_bytes([2, 0, 0, 0])]);
+    /// ```
+    pub fn try_cast_slice<T: Pod>(this: Self) -> Result<Arc<[T]>, (Self, PodCastError)> {
+        // The allocation was laid out for `[u8]`: its data offset and padding only
+        // match those of `[T]` if `T` is not more aligned than the refcount.
+        if align_of::<T>() > align_of::<ArcInner<()>>() {
+            return Err((this, PodCastError::AlignmentMismatch));
+        }
+
+        let len = match bytemuck::try_cast_slice::<u8, T>(&this) {
+            Ok(slice) => slice.len(),
+            Err(e) => return Err((this, e)),
+        };
+        debug_assert_eq!(len * size_of::<T>(), this.len());
+
+        let inner = Arc::into_raw_inner(this);
+
+        // Safety:
+        // - `ArcInner<[u8]>` and `ArcInner<[T]>` have the same data offset and size
+        //   (hence layout) for the same number of bytes, since `T` isn't over-aligned
+        // - the bytes are valid `T`s, since `T: Pod`
+        unsafe {
+            let inner = ptr::slice_from_raw_parts_mut(inner as *mut T, len) as *mut ArcInner<[T]>;
+            Ok(Arc::from_raw_inner(inner))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Arc;
+    use alloc::vec::Vec;
+    use bytemuck::PodCastError;
+    use core::convert::TryInto;
+
+    #[test]
+    fn try_cast_slice_u32() {
+        let bytes: [u8; 16] = [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0];
+        let arc: Arc<[u8]> = Arc::from(&bytes[..]);
+
+        let ints = Arc::try_cast_slice::<u32>(arc).unwrap();
+        assert_eq!(ints.len(), 4);
+        // `bytes` itself isn't necessarily aligned for `u32`
+        let expected: Vec<u32> = bytes
+            .chunks(4)
+            .map(|chunk| u32::from_ne_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(&*ints, &expected[..]);
+    }
+
+    #[test]
+    fn try_cast_slice_odd_length() {
+        let arc: Arc<[u8]> = Arc::from(&[0u8; 15][..]);
+
+        let (arc, e) = Arc::try_cast_slice::<u32>(arc).unwrap_err();
+        assert_eq!(e, PodCastError::OutputSliceWouldHaveSlop);
+        assert_eq!(arc.len(), 15);
+        assert_eq!(Arc::count(&arc), 1);
+    }
+
+    #[test]
+    fn try_cast_slice_over_aligned() {
+        #[derive(Clone, Copy, Debug)]
+        #[repr(C, align(64))]
+        struct OverAligned([u8; 64]);
+        unsafe impl bytemuck::Zeroable for OverAligned {}
+        unsafe impl bytemuck::Pod for OverAligned {}
+
+        let arc: Arc<[u8]> = Arc::from(&[0u8; 64][..]);
+
+        let (arc, e) = Arc::try_cast_slice::<OverAligned>(arc).unwrap_err();
+        assert_eq!(e, PodCastError::AlignmentMismatch);
+        assert_eq!(arc.len(), 64);
+    }
+
+    #[test]
+    fn try_cast_slice_empty() {
+        let arc: Arc<[u8]> = Arc::from(&[][..]);
+
+        let empty = Arc::try_cast_slice::<u64>(arc).unwrap();
+        assert!(empty.is_empty());
+    }
+}
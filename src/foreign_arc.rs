@@ -0,0 +1,184 @@
+use core::borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::ptr::NonNull;
+use core::slice;
+
+use crate::Arc;
+
+/// An atomically reference counted byte slice in memory the crate didn't
+/// allocate, such as a memory-mapped file.
+///
+/// An `Arc<[u8]>` stores its refcount right before the bytes, so it can't point
+/// to foreign memory. A `ForeignArc` instead points to an `Arc` holding the
+/// pointer, length and destructor of the bytes: the destructor is called once
+/// the last clone is dropped, in place of the global deallocator.
+///
+/// ```
+/// use triomphe::ForeignArc;
+///
+/// fn free(ptr: *mut u8, len: usize) {
+///     // Safety: the bytes come from `Box::into_raw` below
+///     drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) });
+/// }
+///
+/// let bytes = Box::into_raw(b"mapped".to_vec().into_boxed_slice()) as *mut u8;
+/// // Safety: `bytes` stays valid until `free` is called
+/// let arc = unsafe { ForeignArc::from_raw_parts_with_dtor(bytes, 6, free) };
+/// assert_eq!(&*arc.clone(), b"mapped");
+/// ```
+#[derive(Clone)]
+pub struct ForeignArc(Arc<ForeignBytes>);
+
+struct ForeignBytes {
+    ptr: NonNull<u8>,
+    len: usize,
+    dtor: fn(*mut u8, usize),
+}
+
+// Safety: the bytes are only ever read, and the one calling
+// `from_raw_parts_with_dtor` guarantees that `dtor` can run on any thread
+unsafe impl Send for ForeignBytes {}
+unsafe impl Sync for ForeignBytes {}
+
+impl Drop for ForeignBytes {
+    fn drop(&mut self) {
+        (self.dtor)(self.ptr.as_ptr(), self.len)
+    }
+}
+
+impl ForeignArc {
+    /// Wraps `len` bytes at `ptr`, calling `dtor(ptr, len)` once the last
+    /// `ForeignArc` to them is dropped. `dtor` receives `ptr` as given, so if it
+    /// needs to write through it, `ptr` must come from a mutable pointer.
+    ///
+    /// This allocates for the refcount, but doesn't copy the bytes.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must be non-null, and valid for reads of `len` bytes until `dtor`
+    ///   is called with it.
+    /// - The bytes must not be mutated until then.
+    /// - `dtor` may be called on any thread, so it must be fine to release the
+    ///   bytes from another thread than the current one.
+    #[inline]
+    pub unsafe fn from_raw_parts_with_dtor(
+        ptr: *const u8,
+        len: usize,
+        dtor: fn(*mut u8, usize),
+    ) -> Self {
+        debug_assert!(!ptr.is_null());
+        ForeignArc(Arc::new(ForeignBytes {
+            ptr: NonNull::new_unchecked(ptr as *mut u8),
+            len,
+            dtor,
+        }))
+    }
+
+    /// Returns the bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        // Safety: the caller of `from_raw_parts_with_dtor` guarantees that the
+        // bytes are valid until `dtor` is called, which happens once the last
+        // `ForeignArc` is dropped
+        unsafe { slice::from_raw_parts(self.0.ptr.as_ptr(), self.0.len) }
+    }
+
+    /// Returns true if the two `ForeignArc`s come from the same call to
+    /// [`from_raw_parts_with_dtor`](ForeignArc::from_raw_parts_with_dtor).
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+
+    /// The reference count of this `ForeignArc`.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        Arc::count(&this.0)
+    }
+}
+
+impl Deref for ForeignArc {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for ForeignArc {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl borrow::Borrow<[u8]> for ForeignArc {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl PartialEq for ForeignArc {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ForeignArc::ptr_eq(self, other) || self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for ForeignArc {}
+
+impl Hash for ForeignArc {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state)
+    }
+}
+
+impl fmt::Debug for ForeignArc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_bytes(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ForeignArc;
+    use alloc::boxed::Box;
+    use alloc::vec;
+    use core::ptr;
+    use core::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+    static FREED: AtomicUsize = AtomicUsize::new(0);
+
+    fn free(ptr: *mut u8, len: usize) {
+        FREED.fetch_add(len, SeqCst);
+        // Safety: the bytes come from `Box::<[u8]>::into_raw`
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, len)) });
+    }
+
+    #[test]
+    fn foreign_dtor_runs_once_on_last_drop() {
+        let ptr = Box::into_raw(vec![7u8; 5].into_boxed_slice()) as *mut u8;
+
+        // Safety: `ptr` is valid until `free` is called
+        let arc = unsafe { ForeignArc::from_raw_parts_with_dtor(ptr, 5, free) };
+        assert_eq!(&*arc, [7; 5]);
+        // Not copied
+        assert_eq!(arc.as_ptr(), ptr as *const u8);
+
+        let clone = arc.clone();
+        assert!(ForeignArc::ptr_eq(&arc, &clone));
+        assert_eq!(ForeignArc::strong_count(&arc), 2);
+
+        drop(arc);
+        assert_eq!(FREED.load(SeqCst), 0);
+        assert_eq!(clone.len(), 5);
+
+        drop(clone);
+        assert_eq!(FREED.load(SeqCst), 5);
+    }
+}
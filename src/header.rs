@@ -1,17 +1,44 @@
-use alloc::alloc::Layout;
+use alloc::alloc::{dealloc, handle_alloc_error, Layout};
 use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::cmp::Ordering;
+use core::convert::TryFrom;
+#[cfg(feature = "nightly")]
+use core::iter::TrustedLen;
 use core::iter::{ExactSizeIterator, Iterator};
-use core::marker::PhantomData;
-use core::mem::{self, ManuallyDrop};
-use core::ptr::{self, addr_of_mut};
+use core::mem::ManuallyDrop;
+use core::ptr::{self, addr_of_mut, NonNull};
 
-use super::{Arc, ArcInner};
+#[cfg(feature = "serde")]
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
+
+use super::{layout_for_value_layout, AllocError, Arc, ArcInner, ArcSliceBuilder};
 
 /// Structure to allow Arc-managing some fixed-sized data and a variably-sized
 /// slice in a single allocation.
+///
+/// As with `std::sync::Arc`, an `Arc<HeaderSlice<H, [T]>>` is only `Send` and `Sync`
+/// if both `H` and `T` are `Send + Sync`:
+///
+/// ```compile_fail
+/// use std::cell::Cell;
+/// use triomphe::{Arc, HeaderSlice};
+///
+/// fn assert_send<T: Send>() {}
+///
+/// // `Cell` is `Send`, but not `Sync`
+/// assert_send::<Arc<HeaderSlice<(), [Cell<u8>]>>>();
+/// ```
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use triomphe::{Arc, HeaderSlice, HeaderWithLength};
+///
+/// fn assert_sync<T: Sync>() {}
+///
+/// assert_sync::<Arc<HeaderSlice<HeaderWithLength<Rc<u8>>, [u8]>>>();
+/// ```
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 #[repr(C)]
 pub struct HeaderSlice<H, T: ?Sized> {
@@ -25,31 +52,116 @@ pub struct HeaderSlice<H, T: ?Sized> {
 impl<H, T> Arc<HeaderSlice<H, [T]>> {
     /// Creates an Arc for a HeaderSlice using the given header struct and
     /// iterator to generate the slice. The resulting Arc will be fat.
-    pub fn from_header_and_iter<I>(header: H, mut items: I) -> Self
+    ///
+    /// If the iterator panics, the header and the elements yielded so far are dropped.
+    pub fn from_header_and_iter<I>(header: H, items: I) -> Self
     where
         I: Iterator<Item = T> + ExactSizeIterator,
     {
-        assert_ne!(mem::size_of::<T>(), 0, "Need to think about ZST");
+        let num_items = items.len();
+
+        Self::try_from_header_and_iter(header, items).unwrap_or_else(|_| {
+            handle_alloc_error(layout_for_value_layout(Self::header_and_slice_layout(
+                num_items,
+            )))
+        })
+    }
 
+    /// Creates an Arc for a HeaderSlice using the given header struct and
+    /// iterator to generate the slice, returning an error if the allocation fails.
+    /// The resulting Arc will be fat.
+    ///
+    /// On failure, the iterator is not advanced and nothing is written.
+    /// If the iterator panics, the header and the elements yielded so far are dropped.
+    pub fn try_from_header_and_iter<I>(header: H, mut items: I) -> Result<Self, AllocError>
+    where
+        I: Iterator<Item = T> + ExactSizeIterator,
+    {
         let num_items = items.len();
 
+        let inner = Arc::try_allocate_for_header_and_slice(num_items)?;
+
+        unsafe {
+            // Safety: inner is a valid pointer, so this can't go out of bounds
+            let dst = addr_of_mut!((*inner.as_ptr()).data.header);
+
+            // Safety: `dst` is valid for writes (just allocated)
+            ptr::write(dst, header);
+        }
+
+        // Write the data.
+        //
+        // Note that any panics here (i.e. from the iterator) are safe, since
+        // the guard drops the header and the elements written so far, then
+        // frees the allocation.
+        //
+        // Safety: the header is initialized and no element is
+        let mut guard = unsafe { PartialHeaderSlice::new(inner, num_items) };
+        for _ in 0..num_items {
+            let item = items
+                .next()
+                .expect("ExactSizeIterator over-reported length");
+
+            // Safety: we write at most `num_items` elements
+            unsafe { guard.push(item) };
+        }
+        assert!(
+            items.next().is_none(),
+            "ExactSizeIterator under-reported length"
+        );
+
+        // Safety: ptr is valid & the inner structure is fully initialized
+        Ok(unsafe { Arc::from_inner(guard.finish()) })
+    }
+
+    /// Creates an Arc for a HeaderSlice using the given header struct and the
+    /// concatenation of several iterators to generate the slice, in a single
+    /// allocation. The resulting Arc will be fat.
+    ///
+    /// If an iterator panics, the header and the elements yielded so far are dropped.
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let arc = Arc::from_header_and_iters("header", &mut [&mut (0..2), &mut (5..7)]);
+    /// assert_eq!(arc.slice, [0, 1, 5, 6]);
+    /// ```
+    pub fn from_header_and_iters(
+        header: H,
+        iters: &mut [&mut dyn ExactSizeIterator<Item = T>],
+    ) -> Self {
+        let num_items = iters
+            .iter()
+            .try_fold(0usize, |sum, items| sum.checked_add(items.len()))
+            .expect("capacity overflow");
+
         let inner = Arc::allocate_for_header_and_slice(num_items);
 
         unsafe {
-            // Write the data.
-            //
-            // Note that any panics here (i.e. from the iterator) are safe, since
-            // we'll just leak the uninitialized memory.
-            ptr::write(&mut ((*inner.as_ptr()).data.header), header);
-            let mut current = (*inner.as_ptr()).data.slice.as_mut_ptr();
-            for _ in 0..num_items {
-                ptr::write(
-                    current,
-                    items
-                        .next()
-                        .expect("ExactSizeIterator over-reported length"),
+            // Safety: inner is a valid pointer, so this can't go out of bounds
+            let dst = addr_of_mut!((*inner.as_ptr()).data.header);
+
+            // Safety: `dst` is valid for writes (just allocated)
+            ptr::write(dst, header);
+        }
+
+        // Write the data, see `from_header_and_iter`.
+        //
+        // Safety: the header is initialized and no element is
+        let mut guard = unsafe { PartialHeaderSlice::new(inner, num_items) };
+        for items in iters.iter_mut() {
+            for _ in 0..items.len() {
+                let item = items
+                    .next()
+                    .expect("ExactSizeIterator over-reported length");
+
+                // The lengths may have changed since we summed them
+                assert!(
+                    guard.initialized < num_items,
+                    "ExactSizeIterator over-reported length"
                 );
-                current = current.offset(1);
+                // Safety: we just checked that we write at most `num_items` elements
+                unsafe { guard.push(item) };
             }
             assert!(
                 items.next().is_none(),
@@ -58,10 +170,105 @@ impl<H, T> Arc<HeaderSlice<H, [T]>> {
         }
 
         // Safety: ptr is valid & the inner structure is fully initialized
-        Arc {
-            p: inner,
-            phantom: PhantomData,
+        unsafe { Arc::from_inner(guard.finish()) }
+    }
+
+    /// Creates an Arc for a HeaderSlice using the given header struct and
+    /// iterator to generate the slice. The resulting Arc will be fat.
+    ///
+    /// Unlike [`from_header_and_iter`](Self::from_header_and_iter), this does not
+    /// check the length reported by the iterator, relying on the [`TrustedLen`]
+    /// contract instead. An iterator whose `TrustedLen` implementation is incorrect
+    /// will cause undefined behavior.
+    ///
+    /// This is not measurably faster for iterators which are also `ExactSizeIterator`s,
+    /// as the optimizer usually removes those checks (see `benches/from_iter.rs`), but
+    /// it accepts `TrustedLen` iterators which aren't, such as `Range<u64>` or a
+    /// [`chain`](Iterator::chain) of slice iterators.
+    ///
+    /// If the iterator panics, the header and the elements yielded so far are dropped.
+    ///
+    /// [`TrustedLen`]: core::iter::TrustedLen
+    #[cfg(feature = "nightly")]
+    pub fn from_header_and_trusted_len_iter<I>(header: H, items: I) -> Self
+    where
+        I: TrustedLen<Item = T>,
+    {
+        let num_items = items
+            .size_hint()
+            .1
+            .expect("TrustedLen iterator longer than usize::MAX");
+
+        let inner = Arc::allocate_for_header_and_slice(num_items);
+
+        unsafe {
+            // Safety: inner is a valid pointer, so this can't go out of bounds
+            let dst = addr_of_mut!((*inner.as_ptr()).data.header);
+
+            // Safety: `dst` is valid for writes (just allocated)
+            ptr::write(dst, header);
+        }
+
+        // Safety: the header is initialized and no element is
+        let mut guard = unsafe { PartialHeaderSlice::new(inner, num_items) };
+
+        // Safety: `TrustedLen` guarantees that exactly `num_items` elements are yielded
+        items.for_each(|item| unsafe { guard.push(item) });
+
+        // Safety: ptr is valid & the inner structure is fully initialized
+        unsafe { Arc::from_inner(guard.finish()) }
+    }
+
+    /// Creates an Arc for a HeaderSlice using the given header struct and
+    /// iterator to generate the slice, with a known length. The resulting Arc
+    /// will be fat.
+    ///
+    /// This is useful for iterators which are not [`ExactSizeIterator`], but whose
+    /// length is known by other means, e.g. from a count stored next to the data,
+    /// as it avoids collecting them into a `Vec` first.
+    ///
+    /// If the iterator panics, the header and the elements yielded so far are dropped.
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let words = "one two three";
+    /// let count = 3;
+    /// let arc = Arc::from_header_and_iter_with_len("words", count, words.split(' '));
+    /// assert_eq!(arc.slice, ["one", "two", "three"]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` yields fewer than `len` elements. Only the first `len`
+    /// elements are used; yielding more is a bug, which is only checked with debug
+    /// assertions enabled.
+    pub fn from_header_and_iter_with_len<I>(header: H, len: usize, mut items: I) -> Self
+    where
+        I: Iterator<Item = T>,
+    {
+        let inner = Arc::allocate_for_header_and_slice(len);
+
+        // Safety: inner is a valid pointer, and `dst` is valid for writes (just allocated)
+        unsafe { ptr::write(addr_of_mut!((*inner.as_ptr()).data.header), header) };
+
+        // Safety: the header is initialized and no element is
+        let mut guard = unsafe { PartialHeaderSlice::new(inner, len) };
+        for item in items.by_ref().take(len) {
+            // Safety: `take` yields at most `len` elements
+            unsafe { guard.push(item) };
         }
+        assert_eq!(
+            guard.initialized, len,
+            "iterator yielded fewer than `len` elements"
+        );
+        debug_assert!(
+            items.next().is_none(),
+            "iterator yielded more than `len` elements"
+        );
+
+        // Safety: ptr is valid & the inner structure is fully initialized
+        unsafe { Arc::from_inner(guard.finish()) }
     }
 
     /// Creates an Arc for a HeaderSlice using the given header struct and
@@ -70,8 +277,6 @@ impl<H, T> Arc<HeaderSlice<H, [T]>> {
     where
         T: Copy,
     {
-        assert_ne!(mem::size_of::<T>(), 0, "Need to think about ZST");
-
         let num_items = items.len();
 
         let inner = Arc::allocate_for_header_and_slice(num_items);
@@ -84,10 +289,112 @@ impl<H, T> Arc<HeaderSlice<H, [T]>> {
         }
 
         // Safety: ptr is valid & the inner structure is fully initialized
-        Arc {
-            p: inner,
-            phantom: PhantomData,
+        unsafe { Arc::from_inner(inner) }
+    }
+
+    /// Creates an Arc for a HeaderSlice using the given header struct and
+    /// a slice to copy, returning an error if the allocation fails.
+    /// The resulting Arc will be fat.
+    pub fn try_from_header_and_slice(header: H, items: &[T]) -> Result<Self, AllocError>
+    where
+        T: Copy,
+    {
+        let num_items = items.len();
+
+        let inner = Arc::try_allocate_for_header_and_slice(num_items)?;
+
+        unsafe {
+            // Write the data.
+            ptr::write(addr_of_mut!((*inner.as_ptr()).data.header), header);
+            let dst = addr_of_mut!((*inner.as_ptr()).data.slice) as *mut T;
+            ptr::copy_nonoverlapping(items.as_ptr(), dst, num_items);
+        }
+
+        // Safety: ptr is valid & the inner structure is fully initialized
+        Ok(unsafe { Arc::from_inner(inner) })
+    }
+
+    /// Creates an Arc for a HeaderSlice using the given header struct and
+    /// a slice whose elements are cloned into the allocation. The resulting Arc will be fat.
+    ///
+    /// If cloning an element panics, the header and the elements cloned so far are dropped.
+    pub fn from_header_and_slice_cloned(header: H, items: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        let num_items = items.len();
+
+        let inner = Arc::allocate_for_header_and_slice(num_items);
+
+        unsafe {
+            // Safety: inner is a valid pointer, so this can't go out of bounds
+            let dst = addr_of_mut!((*inner.as_ptr()).data.header);
+
+            // Safety: `dst` is valid for writes (just allocated)
+            ptr::write(dst, header);
+        }
+
+        // Safety: the header is initialized and no element is
+        let mut guard = unsafe { PartialHeaderSlice::new(inner, num_items) };
+
+        for item in items {
+            // Safety: `guard.initialized < num_items` since `items` has `num_items` elements
+            unsafe { guard.push(item.clone()) };
         }
+
+        // Safety: ptr is valid & the inner structure is fully initialized
+        unsafe { Arc::from_inner(guard.finish()) }
+    }
+
+    /// Creates an Arc for a HeaderSlice using the given header struct and
+    /// `len` elements produced by `f`, stopping at the first error.
+    /// The resulting Arc will be fat.
+    ///
+    /// The outer `Err` is returned if the allocation fails, the inner one if `f` does.
+    /// On either error, the header and the elements produced so far are dropped.
+    #[cfg(any(feature = "borsh", feature = "rkyv"))]
+    pub(crate) fn try_from_header_and_fn<E, F>(
+        header: H,
+        len: usize,
+        mut f: F,
+    ) -> Result<Result<Self, E>, AllocError>
+    where
+        F: FnMut() -> Result<T, E>,
+    {
+        let inner = Arc::try_allocate_for_header_and_slice(len)?;
+
+        unsafe {
+            // Safety: inner is a valid pointer, so this can't go out of bounds
+            let dst = addr_of_mut!((*inner.as_ptr()).data.header);
+
+            // Safety: `dst` is valid for writes (just allocated)
+            ptr::write(dst, header);
+        }
+
+        // Safety: the header is initialized and no element is
+        let mut guard = unsafe { PartialHeaderSlice::new(inner, len) };
+        for _ in 0..len {
+            match f() {
+                // Safety: we write at most `len` elements
+                Ok(item) => unsafe { guard.push(item) },
+                Err(e) => return Ok(Err(e)),
+            }
+        }
+
+        // Safety: ptr is valid & the inner structure is fully initialized
+        Ok(Ok(unsafe { Arc::from_inner(guard.finish()) }))
+    }
+
+    /// Like [`try_from_header_and_fn`](Self::try_from_header_and_fn), but aborts
+    /// if the allocation fails.
+    #[cfg(feature = "rkyv")]
+    pub(crate) fn from_header_and_fn<E, F>(header: H, len: usize, f: F) -> Result<Self, E>
+    where
+        F: FnMut() -> Result<T, E>,
+    {
+        Self::try_from_header_and_fn(header, len, f).unwrap_or_else(|_| {
+            handle_alloc_error(layout_for_value_layout(Self::header_and_slice_layout(len)))
+        })
     }
 
     /// Creates an Arc for a HeaderSlice using the given header struct and
@@ -124,9 +431,131 @@ impl<H, T> Arc<HeaderSlice<H, [T]>> {
         }
 
         // Safety: ptr is valid & the inner structure is fully initialized
-        Arc {
-            p: inner,
-            phantom: PhantomData,
+        unsafe { Arc::from_inner(inner) }
+    }
+
+    /// Returns the size in bytes of the allocation backing this `Arc`, including
+    /// the reference count and any padding.
+    ///
+    /// This is useful for memory accounting, together with [`Arc::heap_ptr`].
+    #[inline]
+    pub fn allocation_size(&self) -> usize {
+        layout_for_value_layout(Self::header_and_slice_layout(self.slice.len())).size()
+    }
+}
+
+impl<H: Clone, T: Clone> Arc<HeaderSlice<H, [T]>> {
+    /// Makes a mutable reference to the header and slice, cloning them into
+    /// a new allocation if this `Arc` is shared.
+    ///
+    /// This is the equivalent of [`Arc::make_mut`] for header-slice `Arc`s: if
+    /// this `Arc` is uniquely owned, the existing allocation is reused without
+    /// copying anything. (It can't share its name, as calls to `Arc::make_mut`
+    /// would then be ambiguous.)
+    #[inline]
+    pub fn make_header_slice_mut(this: &mut Self) -> &mut HeaderSlice<H, [T]> {
+        if !this.is_unique() {
+            // Another pointer exists; clone
+            *this = Arc::from_header_and_slice_cloned(this.header.clone(), &this.slice);
+        }
+
+        unsafe {
+            // Safety: the reference count is 1 at this point, and we required
+            // the Arc itself to be `mut`, so we're returning the only possible
+            // reference to the inner data.
+            &mut (*this.ptr()).data
+        }
+    }
+}
+
+impl<T: Clone> Arc<[T]> {
+    /// Makes a mutable reference to the slice, cloning its elements into a new
+    /// allocation if this `Arc` is shared.
+    ///
+    /// This is the equivalent of [`Arc::make_mut`] for slices: if this `Arc` is
+    /// uniquely owned, the existing allocation is reused without copying anything.
+    #[inline]
+    pub fn make_slice_mut(this: &mut Self) -> &mut [T] {
+        if !this.is_unique() {
+            // Another pointer exists; clone
+            *this = Arc::from(&**this);
+        }
+
+        unsafe {
+            // Safety: the reference count is 1 at this point, and we required
+            // the Arc itself to be `mut`, so we're returning the only possible
+            // reference to the inner data.
+            &mut (*this.ptr()).data
+        }
+    }
+}
+
+/// A freshly allocated `ArcInner<HeaderSlice<H, [T]>>` whose header is initialized
+/// and whose slice is being filled from the front.
+///
+/// If dropped before [`finish`](Self::finish) is called (e.g. while unwinding out
+/// of an element constructor), the header and the elements written so far are dropped
+/// and the allocation is freed.
+struct PartialHeaderSlice<H, T> {
+    inner: NonNull<ArcInner<HeaderSlice<H, [T]>>>,
+    len: usize,
+    initialized: usize,
+}
+
+impl<H, T> PartialHeaderSlice<H, T> {
+    /// # Safety
+    ///
+    /// `inner` must come from `Arc::allocate_for_header_and_slice(len)`
+    /// (or its fallible counterpart),
+    /// its header must be initialized and it must not be used elsewhere.
+    unsafe fn new(inner: NonNull<ArcInner<HeaderSlice<H, [T]>>>, len: usize) -> Self {
+        PartialHeaderSlice {
+            inner,
+            len,
+            initialized: 0,
+        }
+    }
+
+    fn slice_ptr(&self) -> *mut T {
+        // Safety: inner is a valid pointer, so this can't go out of bounds
+        unsafe { addr_of_mut!((*self.inner.as_ptr()).data.slice) as *mut T }
+    }
+
+    /// Writes the next element of the slice.
+    ///
+    /// # Safety
+    ///
+    /// Less than `len` elements must have been written so far.
+    #[inline]
+    unsafe fn push(&mut self, item: T) {
+        debug_assert!(self.initialized < self.len);
+        ptr::write(self.slice_ptr().add(self.initialized), item);
+        self.initialized += 1;
+    }
+
+    /// Returns the now fully initialized allocation.
+    #[inline]
+    fn finish(self) -> NonNull<ArcInner<HeaderSlice<H, [T]>>> {
+        assert_eq!(self.initialized, self.len);
+        ManuallyDrop::new(self).inner
+    }
+}
+
+impl<H, T> Drop for PartialHeaderSlice<H, T> {
+    fn drop(&mut self) {
+        unsafe {
+            // Safety: the header and `initialized` elements have been written
+            ptr::drop_in_place(addr_of_mut!((*self.inner.as_ptr()).data.header));
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.slice_ptr(),
+                self.initialized,
+            ));
+
+            // Safety: this is the layout `allocate_for_header_and_slice` allocated with
+            let layout = layout_for_value_layout(
+                Arc::<HeaderSlice<H, [T]>>::header_and_slice_layout(self.len),
+            );
+            dealloc(self.inner.as_ptr() as *mut u8, layout);
         }
     }
 }
@@ -145,8 +574,178 @@ impl<H> Arc<HeaderSlice<H, str>> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<H: Serialize, T: ?Sized + Serialize> Serialize for HeaderSlice<H, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::ser::Serializer,
+    {
+        serialize_header_slice(serializer, &self.header, &self.slice)
+    }
+}
+
+/// Serializes the fields of a `HeaderSlice`, as a struct with `header` and `slice`
+/// fields. This is also used by `ThinArc`, so that both have the same format.
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_header_slice<S, H, T>(
+    serializer: S,
+    header: &H,
+    slice: &T,
+) -> Result<S::Ok, S::Error>
+where
+    S: ::serde::ser::Serializer,
+    H: Serialize,
+    T: ?Sized + Serialize,
+{
+    let mut state = serializer.serialize_struct("HeaderSlice", 2)?;
+    state.serialize_field("header", header)?;
+    state.serialize_field("slice", slice)?;
+    state.end()
+}
+
+#[cfg(feature = "serde")]
+impl<'de, H: Deserialize<'de>, T: Deserialize<'de>> Deserialize<'de> for Arc<HeaderSlice<H, [T]>> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: ::serde::de::Deserializer<'de>,
+    {
+        let (header, slice): (H, Vec<T>) = deserialize_header_slice(deserializer)?;
+        Ok(Arc::from_header_and_vec(header, slice))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, H: Deserialize<'de>> Deserialize<'de> for Arc<HeaderSlice<H, str>> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: ::serde::de::Deserializer<'de>,
+    {
+        let (header, slice): (H, String) = deserialize_header_slice(deserializer)?;
+        Ok(Arc::from_header_and_str(header, &slice))
+    }
+}
+
+/// Deserializes the fields of a `HeaderSlice`, as serialized by `serialize_header_slice`,
+/// with `S` being an owned version of the slice.
+///
+/// Like a derived impl, this also accepts a `(header, slice)` sequence, which is
+/// what non-self-describing formats such as bincode encode structs as.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_header_slice<'de, D, H, S>(deserializer: D) -> Result<(H, S), D::Error>
+where
+    D: ::serde::de::Deserializer<'de>,
+    H: Deserialize<'de>,
+    S: Deserialize<'de>,
+{
+    use core::fmt;
+    use core::marker::PhantomData;
+    use serde::de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
+    const FIELDS: &[&str] = &["header", "slice"];
+
+    enum Field {
+        Header,
+        Slice,
+        Ignore,
+    }
+
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: ::serde::de::Deserializer<'de>,
+        {
+            struct FieldVisitor;
+
+            impl<'de> Visitor<'de> for FieldVisitor {
+                type Value = Field;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("field identifier")
+                }
+
+                fn visit_u64<E: de::Error>(self, value: u64) -> Result<Field, E> {
+                    Ok(match value {
+                        0 => Field::Header,
+                        1 => Field::Slice,
+                        _ => Field::Ignore,
+                    })
+                }
+
+                fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+                    self.visit_bytes(value.as_bytes())
+                }
+
+                fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Field, E> {
+                    Ok(match value {
+                        b"header" => Field::Header,
+                        b"slice" => Field::Slice,
+                        _ => Field::Ignore,
+                    })
+                }
+            }
+
+            deserializer.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    struct HeaderSliceVisitor<H, S>(PhantomData<(H, S)>);
+
+    impl<'de, H: Deserialize<'de>, S: Deserialize<'de>> Visitor<'de> for HeaderSliceVisitor<H, S> {
+        type Value = (H, S);
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("struct HeaderSlice")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let header = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let slice = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            Ok((header, slice))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut header = None;
+            let mut slice = None;
+            while let Some(field) = map.next_key()? {
+                match field {
+                    Field::Header => {
+                        if header.is_some() {
+                            return Err(de::Error::duplicate_field("header"));
+                        }
+                        header = Some(map.next_value()?);
+                    }
+                    Field::Slice => {
+                        if slice.is_some() {
+                            return Err(de::Error::duplicate_field("slice"));
+                        }
+                        slice = Some(map.next_value()?);
+                    }
+                    Field::Ignore => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            let header = header.ok_or_else(|| de::Error::missing_field("header"))?;
+            let slice = slice.ok_or_else(|| de::Error::missing_field("slice"))?;
+            Ok((header, slice))
+        }
+    }
+
+    deserializer.deserialize_struct("HeaderSlice", FIELDS, HeaderSliceVisitor(PhantomData))
+}
+
 /// Header data with an inline length. Consumers that use HeaderWithLength as the
 /// Header type in HeaderSlice can take advantage of ThinArc.
+///
+/// The length must match the one of the slice it is stored with, so it can't be
+/// modified once created: use the constructors which compute it from the
+/// items, such as [`ThinArc::from_header_and_iter`](crate::ThinArc::from_header_and_iter),
+/// [`Arc::from_header_with_length_and_iter`], [`Arc::from_header_with_length_and_slice`]
+/// or [`Arc::from_header_with_length_and_vec`].
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 #[repr(C)]
 pub struct HeaderWithLength<H> {
@@ -154,17 +753,36 @@ pub struct HeaderWithLength<H> {
     pub header: H,
 
     /// The slice length.
-    pub length: usize,
+    pub(crate) length: usize,
 }
 
 impl<H> HeaderWithLength<H> {
     /// Creates a new HeaderWithLength.
+    ///
+    /// Nothing checks that `length` matches the slice the header is stored with,
+    /// and a `ThinArc` created from a mismatched `Arc` panics.
+    #[deprecated(
+        since = "0.2.0",
+        note = "the length can get out of sync with the slice: use `ThinArc::from_header_and_iter` \
+                or `Arc::from_header_with_length_and_{iter,slice,vec}`, which compute it"
+    )]
     #[inline]
     pub fn new(header: H, length: usize) -> Self {
         HeaderWithLength { header, length }
     }
+
+    /// Returns the slice length.
+    #[inline]
+    pub fn length(&self) -> usize {
+        self.length
+    }
 }
 
+/// Drops the `()` header, e.g. to turn an `Arc<HeaderSlice<(), [T]>>` into an `Arc<[T]>`.
+///
+/// `HeaderSlice` is `repr(C)` and `()` is a zero-sized type with an alignment of 1, so
+/// `HeaderSlice<(), T>` has the same layout (and pointer metadata) as `T`. This just
+/// reinterprets the pointer: it neither allocates nor modifies the refcount.
 impl<T: ?Sized> From<Arc<HeaderSlice<(), T>>> for Arc<T> {
     fn from(this: Arc<HeaderSlice<(), T>>) -> Self {
         debug_assert_eq!(
@@ -177,6 +795,9 @@ impl<T: ?Sized> From<Arc<HeaderSlice<(), T>>> for Arc<T> {
     }
 }
 
+/// Adds a `()` header, e.g. to turn an `Arc<[T]>` into an `Arc<HeaderSlice<(), [T]>>`.
+///
+/// Like the opposite conversion, this just reinterprets the pointer.
 impl<T: ?Sized> From<Arc<T>> for Arc<HeaderSlice<(), T>> {
     fn from(this: Arc<T>) -> Self {
         // Safety: `T` and `HeaderSlice<(), T>` has the same layout
@@ -184,9 +805,76 @@ impl<T: ?Sized> From<Arc<T>> for Arc<HeaderSlice<(), T>> {
     }
 }
 
-impl<T: Copy> From<&[T]> for Arc<[T]> {
+impl<T> Arc<[T]> {
+    /// Creates an `Arc<[T]>` from an iterator reporting its exact length, writing
+    /// the elements directly into a single allocation.
+    ///
+    /// Unlike [`FromIterator`](core::iter::FromIterator), which may need to buffer
+    /// the elements first, this never allocates more than once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator yields more or fewer elements than its
+    /// [`ExactSizeIterator::len`] reported.
+    pub fn from_iter_exact<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        Arc::from_header_and_iter((), items.into_iter()).into()
+    }
+
+    /// Creates an `Arc<[T]>` from an iterator of unknown length, preallocating
+    /// room for `capacity` elements.
+    ///
+    /// The elements are written directly into the allocation of the `Arc`, which
+    /// grows if the iterator yields more than `capacity` elements, and is shrunk
+    /// to fit at the end if it yields fewer. If `capacity` is exact, this
+    /// allocates only once. See [`ArcSliceBuilder`](crate::ArcSliceBuilder).
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// // At most 10 elements, but how many isn't known
+    /// let evens: Arc<[u32]> = Arc::from_iter_with_capacity(10, (0..10).filter(|x| x % 2 == 0));
+    /// assert_eq!(*evens, [0, 2, 4, 6, 8]);
+    /// ```
+    pub fn from_iter_with_capacity<I>(capacity: usize, items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut builder = ArcSliceBuilder::with_capacity(capacity);
+        builder.extend(items);
+        builder.finish()
+    }
+
+    /// Creates an empty `Arc<[T]>`.
+    ///
+    /// This still allocates, since the refcount lives next to the (empty) slice,
+    /// but only once: the allocation holds nothing but the refcount.
+    #[inline]
+    pub fn empty() -> Self {
+        Arc::from_header_and_iter((), core::iter::empty()).into()
+    }
+}
+
+impl<T> Default for Arc<[T]> {
+    #[inline]
+    fn default() -> Self {
+        Arc::empty()
+    }
+}
+
+/// Clones the elements into a new allocation.
+///
+/// Unlike std's `Arc`, this can't specialize on `T: Copy` to copy the elements with
+/// a single `memcpy`. The optimizer usually turns the clone loop into one anyway,
+/// but not for every type: e.g. `(u32, u16)` elements, which have padding, are about
+/// 30% slower to convert (see `benches/from_slice.rs`). Use
+/// [`Arc::from_header_and_slice`] with a `()` header to always copy them at once.
+impl<T: Clone> From<&[T]> for Arc<[T]> {
     fn from(slice: &[T]) -> Self {
-        Arc::from_header_and_slice((), slice).into()
+        Arc::from_header_and_slice_cloned((), slice).into()
     }
 }
 
@@ -202,11 +890,21 @@ impl From<String> for Arc<str> {
     }
 }
 
-// FIXME: once `pointer::with_metadata_of` is stable or
-//        implementable on stable without assuming ptr layout
-//        this will be able to accept `T: ?Sized`.
-impl<T> From<Box<T>> for Arc<T> {
-    fn from(b: Box<T>) -> Self {
+impl Default for Arc<str> {
+    #[inline]
+    fn default() -> Self {
+        Arc::from("")
+    }
+}
+
+impl<T> Arc<T> {
+    /// Moves the value out of the given `Box` into a new `Arc`,
+    /// deallocating the box without dropping the value.
+    //
+    // FIXME: once `pointer::with_metadata_of` is stable or
+    //        implementable on stable without assuming ptr layout
+    //        this will be able to accept `T: ?Sized`.
+    pub fn from_box(b: Box<T>) -> Self {
         let layout = Layout::for_value::<T>(&b);
 
         // Safety: the closure only changes the type of the pointer
@@ -232,10 +930,21 @@ impl<T> From<Box<T>> for Arc<T> {
             drop(Box::<ManuallyDrop<T>>::from_raw(src as _));
         }
 
-        Arc {
-            p: inner,
-            phantom: PhantomData,
-        }
+        unsafe { Arc::from_inner(inner) }
+    }
+}
+
+impl<T> From<Box<T>> for Arc<T> {
+    #[inline]
+    fn from(b: Box<T>) -> Self {
+        Arc::from_box(b)
+    }
+}
+
+impl<T> From<Box<[T]>> for Arc<[T]> {
+    #[inline]
+    fn from(b: Box<[T]>) -> Self {
+        Arc::from(Vec::from(b))
     }
 }
 
@@ -245,6 +954,32 @@ impl<T> From<Vec<T>> for Arc<[T]> {
     }
 }
 
+/// Reinterprets an `Arc<[T]>` of exactly `N` elements as an `Arc<[T; N]>`, without
+/// reallocating or modifying the refcount. Otherwise, the `Arc<[T]>` is given back.
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use triomphe::Arc;
+///
+/// let slice: Arc<[u8]> = Arc::from(&[1, 2, 3][..]);
+/// let slice = <Arc<[u8; 4]>>::try_from(slice).unwrap_err();
+/// let array = <Arc<[u8; 3]>>::try_from(slice).unwrap();
+/// assert_eq!(*array, [1, 2, 3]);
+/// ```
+impl<T, const N: usize> TryFrom<Arc<[T]>> for Arc<[T; N]> {
+    type Error = Arc<[T]>;
+
+    fn try_from(arc: Arc<[T]>) -> Result<Self, Self::Error> {
+        if arc.len() == N {
+            // Safety: `ArcInner` is `repr(C)`, so an `ArcInner<[T]>` with `N` elements
+            // has the same layout as an `ArcInner<[T; N]>`
+            Ok(unsafe { Arc::from_raw_inner(Arc::into_raw_inner(arc) as *mut ArcInner<[T; N]>) })
+        } else {
+            Err(arc)
+        }
+    }
+}
+
 pub(crate) type HeaderSliceWithLength<H, T> = HeaderSlice<HeaderWithLength<H>, T>;
 
 impl<H: PartialOrd, T: ?Sized + PartialOrd> PartialOrd for HeaderSliceWithLength<H, T> {
@@ -259,14 +994,37 @@ impl<H: Ord, T: ?Sized + Ord> Ord for HeaderSliceWithLength<H, T> {
     }
 }
 
+impl<H, T> HeaderSliceWithLength<H, [T]> {
+    /// Returns the number of elements in the slice.
+    #[inline]
+    pub fn len(&self) -> usize {
+        debug_assert_eq!(
+            self.header.length,
+            self.slice.len(),
+            "the inline length must match the slice length"
+        );
+        self.slice.len()
+    }
+
+    /// Returns `true` if the slice has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::boxed::Box;
-    use alloc::string::String;
+    use alloc::string::{String, ToString};
     use alloc::vec;
+    use alloc::vec::Vec;
+    use core::convert::TryFrom;
     use core::iter;
+    use core::sync::atomic::{AtomicUsize, Ordering::SeqCst};
 
-    use crate::{Arc, HeaderSlice};
+    use crate::test_drops::{Counted, DropCounter};
+    use crate::{Arc, HeaderSlice, HeaderWithLength};
 
     #[test]
     fn from_header_and_iter_smoke() {
@@ -279,6 +1037,27 @@ mod tests {
         assert_eq!(arc.slice, [1, 2, 3, 4, 5, 6, 7]);
     }
 
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn from_header_and_trusted_len_iter_smoke() {
+        let arc = Arc::from_header_and_trusted_len_iter((42u32, 17u8), 1u16..8);
+
+        assert_eq!(arc.header, (42, 17));
+        assert_eq!(arc.slice, [1, 2, 3, 4, 5, 6, 7]);
+
+        let arc = Arc::from_header_and_trusted_len_iter((), [1u16, 2, 3].iter().map(|x| x * 2));
+        assert_eq!(arc.slice, [2, 4, 6]);
+
+        let arc = Arc::from_header_and_trusted_len_iter((), iter::empty::<u16>());
+        assert_eq!(arc.slice, [0u16; 0]);
+
+        // Neither is an `ExactSizeIterator`
+        let arc = Arc::from_header_and_trusted_len_iter((), 3u64..6);
+        assert_eq!(arc.slice, [3, 4, 5]);
+        let arc = Arc::from_header_and_trusted_len_iter((), [1u8].iter().chain(&[2, 3]));
+        assert_eq!(arc.slice, [&1, &2, &3]);
+    }
+
     #[test]
     fn from_header_and_slice_smoke() {
         let arc = Arc::from_header_and_slice((42u32, 17u8), &[1u16, 2, 3, 4, 5, 6, 7]);
@@ -287,6 +1066,252 @@ mod tests {
         assert_eq!(arc.slice, [1u16, 2, 3, 4, 5, 6, 7]);
     }
 
+    #[test]
+    fn try_from_header_and_slice_smoke() {
+        let arc = Arc::try_from_header_and_slice((42u32, 17u8), &[1u16, 2, 3]).unwrap();
+
+        assert_eq!(arc.header, (42, 17));
+        assert_eq!(arc.slice, [1u16, 2, 3]);
+    }
+
+    #[test]
+    fn try_from_header_and_iter_smoke() {
+        let arc = Arc::try_from_header_and_iter(7u8, (0..4u32).map(|i| i * 2)).unwrap();
+
+        assert_eq!(arc.header, 7);
+        assert_eq!(arc.slice, [0, 2, 4, 6]);
+    }
+
+    #[test]
+    fn try_from_header_and_iter_overflow() {
+        use crate::AllocError;
+
+        // The size of the slice overflows `isize::MAX`, then `usize::MAX`
+        let max = usize::MAX / core::mem::size_of::<u64>();
+        for len in [max - 1, max, max + 1].iter().copied() {
+            let items = (0..len).map(|_| -> u64 { unreachable!() });
+            assert_eq!(
+                Arc::try_from_header_and_iter((), items).err(),
+                Some(AllocError)
+            );
+        }
+
+        let items = (0..usize::MAX).map(|_| -> u8 { unreachable!() });
+        assert_eq!(
+            Arc::try_from_header_and_iter(1u16, items).err(),
+            Some(AllocError)
+        );
+    }
+
+    #[test]
+    #[should_panic = "capacity overflow"]
+    fn from_header_and_iter_overflow() {
+        let _ = Arc::from_header_and_iter((), (0..usize::MAX).map(|_| -> u64 { unreachable!() }));
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", target_pointer_width = "64"))]
+    fn try_from_header_and_iter_failure() {
+        use crate::test_allocator::FAILING_SIZE;
+        use crate::AllocError;
+
+        let drops = DropCounter::new();
+        let mut calls = 0;
+        let items = (0..FAILING_SIZE).map(|_| {
+            calls += 1;
+            0u8
+        });
+        let result = Arc::try_from_header_and_iter(drops.counted(()), items);
+        assert_eq!(result.err(), Some(AllocError));
+
+        // The iterator hasn't been advanced, and the header was dropped without being written
+        assert_eq!(calls, 0);
+        assert_eq!(drops.drops(), 1);
+
+        let arc = Arc::try_from_header_and_iter(drops.counted(()), 0..16u8).unwrap();
+        assert_eq!(arc.slice.len(), 16);
+    }
+
+    #[test]
+    fn from_header_and_slice_cloned_smoke() {
+        let items = [String::from("1"), String::from("2"), String::from("3")];
+        let arc = Arc::from_header_and_slice_cloned((42u32, 17u8), &items);
+
+        assert_eq!(arc.header, (42, 17));
+        assert_eq!(arc.slice, items);
+    }
+
+    #[test]
+    fn from_header_and_slice_cloned_no_leak() {
+        let counter = DropCounter::new();
+        let items = [
+            counter.counted(()),
+            counter.counted(()),
+            counter.counted(()),
+        ];
+        let arc = Arc::from_header_and_slice_cloned(counter.counted(()), &items);
+        assert_eq!(counter.live(), 7);
+
+        drop(arc);
+        assert_eq!(counter.live(), 3);
+
+        drop(items);
+        assert_eq!(counter.live(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_header_and_slice_cloned_panic() {
+        use core::cell::Cell;
+
+        struct PanicOnThirdClone<'a>(&'a Cell<usize>);
+
+        impl Clone for PanicOnThirdClone<'_> {
+            fn clone(&self) -> Self {
+                let clones = self.0.get();
+                if clones == 2 {
+                    panic!("third clone");
+                }
+                self.0.set(clones + 1);
+                PanicOnThirdClone(self.0)
+            }
+        }
+
+        let clones = Cell::new(0);
+        let counter = DropCounter::new();
+        let items: Vec<_> = (0..4)
+            .map(|_| counter.counted(PanicOnThirdClone(&clones)))
+            .collect();
+        let header = counter.counted(PanicOnThirdClone(&clones));
+        assert_eq!(counter.live(), 5);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            Arc::from_header_and_slice_cloned(header, &items)
+        }));
+        assert!(result.is_err());
+
+        // The header and the two successful clones have been dropped
+        assert_eq!(counter.live(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_header_and_iter_panic() {
+        let counter = DropCounter::new();
+        let result = std::panic::catch_unwind(|| {
+            Arc::from_header_and_iter(
+                counter.counted(()),
+                (0..5).map(|i| {
+                    if i == 2 {
+                        panic!("third element");
+                    }
+                    counter.counted(())
+                }),
+            )
+        });
+        assert!(result.is_err());
+
+        // The header and the first two elements have been dropped
+        assert_eq!(counter.live(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_header_and_iter_under_reported_length() {
+        struct Liar<'a>(&'a DropCounter);
+
+        impl Iterator for Liar<'_> {
+            type Item = Counted;
+
+            fn next(&mut self) -> Option<Counted> {
+                Some(self.0.counted(()))
+            }
+        }
+
+        impl ExactSizeIterator for Liar<'_> {
+            fn len(&self) -> usize {
+                3
+            }
+        }
+
+        let counter = DropCounter::new();
+        let result = std::panic::catch_unwind(|| Arc::from_header_and_iter((), Liar(&counter)));
+        assert!(result.is_err());
+        assert_eq!(counter.live(), 0);
+    }
+
+    #[test]
+    fn from_header_and_iter_with_len() {
+        // Not an `ExactSizeIterator`
+        let items = (0..20u32).filter(|x| x % 3 == 0);
+
+        // There are 7 multiples of 3 in 0..20
+        let arc = Arc::from_header_and_iter_with_len("header", 7, items);
+        assert_eq!(arc.header, "header");
+        assert_eq!(arc.slice, [0, 3, 6, 9, 12, 15, 18]);
+
+        let arc = Arc::from_header_and_iter_with_len((), 0, iter::empty::<String>());
+        assert!(arc.slice.is_empty());
+    }
+
+    #[test]
+    #[should_panic = "iterator yielded fewer than `len` elements"]
+    fn from_header_and_iter_with_len_too_short() {
+        let _ = Arc::from_header_and_iter_with_len((), 3, iter::once(String::new()));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "iterator yielded more than `len` elements"]
+    fn from_header_and_iter_with_len_too_long() {
+        let _ = Arc::from_header_and_iter_with_len((), 1, (0..3).map(|i| i.to_string()));
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn from_header_and_iter_with_len_too_long() {
+        let arc = Arc::from_header_and_iter_with_len((), 1, (0..3).map(|i| i.to_string()));
+        assert_eq!(arc.slice, ["0"]);
+    }
+
+    #[test]
+    fn from_iter_with_capacity() {
+        let strings = || (0..8).map(|i| i.to_string()).filter(|s| s != "3");
+        let expected: Vec<String> = strings().collect();
+
+        // Exact, under- and over-estimated capacities
+        for capacity in [7, 0, 2, 100].iter().copied() {
+            let arc: Arc<[String]> = Arc::from_iter_with_capacity(capacity, strings());
+            assert_eq!(*arc, *expected);
+            assert!(arc.is_unique());
+        }
+
+        let arc: Arc<[u8]> = Arc::from_iter_with_capacity(4, iter::empty());
+        assert!(arc.is_empty());
+    }
+
+    #[test]
+    fn slice_to_array() {
+        let slice: Arc<[String]> = Arc::from(vec!["a".to_string(), "b".to_string()]);
+        let clone = slice.clone();
+
+        let slice = <Arc<[String; 3]>>::try_from(slice).unwrap_err();
+        assert!(Arc::ptr_eq(&slice, &clone));
+        let slice = <Arc<[String; 0]>>::try_from(slice).unwrap_err();
+
+        let array = <Arc<[String; 2]>>::try_from(slice).unwrap();
+        assert_eq!(*array, ["a", "b"]);
+        assert_eq!(
+            Arc::as_ptr(&array) as *const String,
+            Arc::as_ptr(&clone) as *const String
+        );
+        assert_eq!(Arc::count(&array), 2);
+
+        drop(clone);
+        let empty = <Arc<[u64; 0]>>::try_from(Arc::<[u64]>::empty()).unwrap();
+        assert!(empty.is_unique());
+    }
+
     #[test]
     fn from_header_and_vec_smoke() {
         let arc = Arc::from_header_and_vec((42u32, 17u8), vec![1u16, 2, 3, 4, 5, 6, 7]);
@@ -296,6 +1321,22 @@ mod tests {
     }
 
     #[test]
+    fn from_header_and_vec_drops_once() {
+        let drops = DropCounter::new();
+        let mut v: Vec<_> = (0..7).map(|_| drops.counted(())).collect();
+        v.reserve(10);
+
+        let arc = Arc::from_header_and_vec(42u32, v);
+        assert_eq!(drops.drops(), 0);
+        assert_eq!(arc.slice.len(), 7);
+
+        drop(arc);
+        assert_eq!(drops.drops(), 7);
+    }
+
+    // `rkyv` makes the type of `[]` ambiguous, see the feature in Cargo.toml
+    #[test]
+    #[cfg(not(feature = "rkyv"))]
     fn from_header_and_iter_empty() {
         let arc = Arc::from_header_and_iter((42u32, 17u8), iter::empty::<u16>());
 
@@ -304,6 +1345,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "rkyv"))]
     fn from_header_and_slice_empty() {
         let arc = Arc::from_header_and_slice((42u32, 17u8), &[1u16; 0]);
 
@@ -312,6 +1354,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "rkyv"))]
     fn from_header_and_vec_empty() {
         let arc = Arc::from_header_and_vec((42u32, 17u8), vec![1u16; 0]);
 
@@ -319,6 +1362,43 @@ mod tests {
         assert_eq!(arc.slice, []);
     }
 
+    #[test]
+    fn from_header_and_iter_zst() {
+        let arc = Arc::from_header_and_iter(42u32, (0..17).map(|_| ()));
+
+        assert_eq!(arc.header, 42);
+        assert_eq!(arc.slice.len(), 17);
+    }
+
+    #[test]
+    fn from_header_and_slice_zst() {
+        let arc: Arc<HeaderSlice<u32, [()]>> = Arc::from_header_and_slice(42u32, &[(); 17]);
+
+        assert_eq!(arc.header, 42);
+        assert_eq!(arc.slice.len(), 17);
+    }
+
+    #[test]
+    fn zst_slice_elements_are_dropped() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        // Zero-sized, unlike `Counted`
+        struct Marker;
+
+        impl Drop for Marker {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, SeqCst);
+            }
+        }
+
+        let arc = Arc::from_header_and_iter(42u32, (0..5).map(|_| Marker));
+        assert_eq!(arc.header, 42);
+        assert_eq!(arc.slice.len(), 5);
+
+        drop(arc);
+        assert_eq!(DROPS.load(SeqCst), 5);
+    }
+
     #[test]
     fn issue_13_empty() {
         crate::Arc::from_header_and_iter((), iter::empty::<usize>());
@@ -346,6 +1426,24 @@ mod tests {
         assert_eq!(&empty.slice, "");
     }
 
+    #[test]
+    fn arc_str_from_str_and_string() {
+        let a = Arc::<str>::from("hello");
+        let s: &str = &a;
+        assert_eq!(s, "hello");
+        assert_eq!(a.len(), 5);
+
+        let b = Arc::<str>::from(String::from("hello"));
+        assert_eq!(a, b);
+        assert!(b.starts_with("hel"));
+
+        let empty = Arc::<str>::from(String::new());
+        assert_eq!(&*empty, "");
+
+        let unicode = Arc::<str>::from("こんにちは");
+        assert_eq!(unicode.chars().count(), 5);
+    }
+
     #[test]
     fn erase_and_create_from_thin_air_header() {
         let a: Arc<HeaderSlice<(), [u32]>> = Arc::from_header_and_slice((), &[12, 17, 16]);
@@ -380,6 +1478,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn arc_slice_from_vec_and_slice() {
+        let empty = Arc::<[u8]>::from(Vec::new());
+        assert!(empty.is_empty());
+
+        let large: Vec<u32> = (0..100_000).collect();
+        let arc = Arc::<[u32]>::from(large.clone());
+        assert_eq!(*arc, *large);
+
+        let arc = Arc::<[u32]>::from(&large[..]);
+        assert_eq!(*arc, *large);
+
+        let strings = [String::from("1"), String::from("2"), String::from("3")];
+        let arc = Arc::<[String]>::from(&strings[..]);
+        assert_eq!(*arc, strings);
+    }
+
+    #[test]
+    fn from_box_moves_once() {
+        let drops = DropCounter::new();
+        let arc = Arc::from_box(Box::new(drops.counted(())));
+        assert_eq!(drops.drops(), 0);
+        drop(arc);
+        assert_eq!(drops.drops(), 1);
+
+        let b: Box<[Counted]> = (0..3).map(|_| drops.counted(())).collect();
+        let arc = Arc::<[Counted]>::from(b);
+        assert_eq!(arc.len(), 3);
+        assert_eq!(drops.drops(), 1);
+        drop(arc);
+        assert_eq!(drops.drops(), 4);
+    }
+
     /// It’s possible to make a generic `Arc` wrapper that supports both:
     ///
     /// * `T: !Sized`
@@ -408,4 +1539,319 @@ mod tests {
         let mut_ref = Arc::make_mut(&mut answer.0);
         mut_ref.slice = 42;
     }
+
+    #[test]
+    fn header_slice_ord() {
+        let mut arcs: Vec<Arc<HeaderSlice<u32, [u8]>>> = vec![
+            Arc::from_header_and_slice(2, &[0]),
+            Arc::from_header_and_slice(1, &[2, 0]),
+            Arc::from_header_and_slice(1, &[1, 5, 5]),
+            Arc::from_header_and_slice(1, &[1, 5]),
+            Arc::from_header_and_slice(0, &[9]),
+        ];
+        arcs.sort();
+
+        let sorted: Vec<(u32, &[u8])> = arcs.iter().map(|a| (a.header, &a.slice)).collect();
+        assert_eq!(
+            sorted,
+            [
+                (0, &[9][..]),
+                (1, &[1, 5]),
+                (1, &[1, 5, 5]),
+                (1, &[2, 0]),
+                (2, &[0]),
+            ]
+        );
+
+        for pair in arcs.windows(2) {
+            assert_eq!(pair[0].partial_cmp(&pair[1]), Some(pair[0].cmp(&pair[1])));
+        }
+
+        let map: alloc::collections::BTreeMap<_, _> = arcs.iter().cloned().zip(0..).collect();
+        assert_eq!(map[&Arc::from_header_and_slice(1, &[2u8, 0][..])], 3);
+    }
+
+    #[test]
+    fn header_slice_make_mut() {
+        let mut unique = Arc::from_header_and_slice(1u8, &[1u32, 2, 3]);
+        let ptr = Arc::as_ptr(&unique);
+        {
+            let data = Arc::make_header_slice_mut(&mut unique);
+            data.header = 2;
+            data.slice[0] = 10;
+        }
+        // Unique: mutated in place
+        assert_eq!(Arc::as_ptr(&unique), ptr);
+        assert_eq!(unique.header, 2);
+        assert_eq!(unique.slice, [10, 2, 3]);
+
+        let shared = unique.clone();
+        Arc::make_header_slice_mut(&mut unique).slice[1] = 20;
+        // Shared: cloned into a new allocation, leaving the other `Arc` untouched
+        assert_ne!(Arc::as_ptr(&unique), ptr);
+        assert_eq!(Arc::as_ptr(&shared), ptr);
+        assert_eq!(unique.slice, [10, 20, 3]);
+        assert_eq!(shared.slice, [10, 2, 3]);
+        assert!(unique.is_unique());
+        assert!(shared.is_unique());
+    }
+
+    #[test]
+    fn slice_make_mut() {
+        let mut unique: Arc<[String]> = Arc::from(&[String::from("a"), String::from("b")][..]);
+        let ptr = Arc::as_ptr(&unique);
+        Arc::make_slice_mut(&mut unique)[0].push('!');
+        // Unique: mutated in place
+        assert_eq!(Arc::as_ptr(&unique), ptr);
+        assert_eq!(*unique, ["a!", "b"]);
+
+        let shared = unique.clone();
+        Arc::make_slice_mut(&mut unique)[1].push('?');
+        // Shared: deep-cloned into a new allocation, leaving the other `Arc` untouched
+        assert_ne!(Arc::as_ptr(&unique), ptr);
+        assert_eq!(Arc::as_ptr(&shared), ptr);
+        assert_eq!(*unique, ["a!", "b?"]);
+        assert_eq!(*shared, ["a!", "b"]);
+        assert!(unique.is_unique());
+        assert!(shared.is_unique());
+    }
+
+    #[test]
+    fn allocation_size() {
+        use core::alloc::Layout;
+        use core::sync::atomic::AtomicUsize;
+
+        let arc = Arc::from_header_and_slice(1u8, &[1u32, 2, 3]);
+        // count, then header and padding up to the slice, then the slice
+        let expected = Layout::new::<AtomicUsize>()
+            .extend(Layout::new::<u8>())
+            .unwrap()
+            .0
+            .extend(Layout::array::<u32>(3).unwrap())
+            .unwrap()
+            .0
+            .pad_to_align();
+        assert_eq!(arc.allocation_size(), expected.size());
+        assert_eq!(
+            arc.allocation_size(),
+            core::mem::size_of::<usize>() + 4 + 3 * 4
+        );
+
+        let empty = Arc::from_header_and_slice(0u64, &[] as &[u8]);
+        assert_eq!(empty.allocation_size(), core::mem::size_of::<usize>() + 8);
+    }
+
+    /// An iterator yielding `actual` elements while reporting `reported`.
+    struct Misreported {
+        actual: usize,
+        reported: usize,
+    }
+
+    impl Iterator for Misreported {
+        type Item = String;
+
+        fn next(&mut self) -> Option<String> {
+            self.actual = self.actual.checked_sub(1)?;
+            self.reported = self.reported.saturating_sub(1);
+            Some(String::from("x"))
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.reported, Some(self.reported))
+        }
+    }
+
+    impl ExactSizeIterator for Misreported {}
+
+    #[test]
+    fn from_iter_exact() {
+        let arc = Arc::<[u32]>::from_iter_exact(vec![1, 2, 3]);
+        assert_eq!(&*arc, &[1, 2, 3]);
+
+        let arc = Arc::<[String]>::from_iter_exact((0..4u8).map(|i| i.to_string()));
+        assert_eq!(arc.len(), 4);
+        assert_eq!(arc[3], "3");
+
+        let empty = Arc::<[u8]>::from_iter_exact(iter::empty());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic = "ExactSizeIterator over-reported length"]
+    fn from_iter_exact_over_reported() {
+        let _ = Arc::from_iter_exact(Misreported {
+            actual: 2,
+            reported: 3,
+        });
+    }
+
+    #[test]
+    #[should_panic = "ExactSizeIterator under-reported length"]
+    fn from_iter_exact_under_reported() {
+        let _ = Arc::from_iter_exact(Misreported {
+            actual: 3,
+            reported: 2,
+        });
+    }
+
+    #[test]
+    fn header_slice_with_length_len() {
+        let arc = Arc::from_header_with_length_and_slice(7u8, &[1u32, 2, 3]);
+        assert_eq!(arc.len(), 3);
+        assert_eq!(arc.len(), arc.slice.len());
+        assert!(!arc.is_empty());
+
+        let empty = Arc::from_header_with_length_and_slice(7u8, &[] as &[u32]);
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+
+        let manual: &HeaderSlice<HeaderWithLength<u8>, [u32]> = &HeaderSlice {
+            header: HeaderWithLength {
+                header: 7,
+                length: 2,
+            },
+            slice: [1, 2],
+        };
+        assert_eq!(manual.len(), 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "the inline length must match the slice length"]
+    #[allow(deprecated)]
+    fn header_slice_with_length_mismatch() {
+        let arc = Arc::from_header_and_slice(HeaderWithLength::new(7u8, 3), &[1u32, 2]);
+        let _ = arc.len();
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    #[allow(deprecated)]
+    fn header_slice_with_length_mismatch() {
+        let arc = Arc::from_header_and_slice(HeaderWithLength::new(7u8, 3), &[1u32, 2]);
+        assert_eq!(arc.len(), 2);
+    }
+
+    #[test]
+    fn from_header_and_iters() {
+        let arc = Arc::from_header_and_iters(
+            7u8,
+            &mut [
+                &mut (0..3u32),
+                &mut iter::empty(),
+                &mut (10..12),
+                &mut [20, 21].iter().copied(),
+            ],
+        );
+        assert_eq!(arc.header, 7);
+        assert_eq!(arc.slice, [0, 1, 2, 10, 11, 20, 21]);
+
+        let empty = Arc::<HeaderSlice<(), [String]>>::from_header_and_iters((), &mut []);
+        assert!(empty.slice.is_empty());
+    }
+
+    #[test]
+    #[should_panic = "ExactSizeIterator under-reported length"]
+    fn from_header_and_iters_under_reported() {
+        let _ = Arc::from_header_and_iters(
+            (),
+            &mut [
+                &mut (0..2).map(|i| i.to_string()),
+                &mut Misreported {
+                    actual: 3,
+                    reported: 2,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn unit_header_conversions() {
+        let slice: Arc<[String]> = Arc::from(vec![String::from("a"), String::from("b")]);
+        let keep = slice.clone();
+
+        let with_header: Arc<HeaderSlice<(), [String]>> = slice.into();
+        assert_eq!(with_header.slice, ["a", "b"]);
+        assert_eq!(with_header.heap_ptr(), keep.heap_ptr());
+        assert_eq!(Arc::count(&keep), 2);
+
+        let slice: Arc<[String]> = with_header.into();
+        assert!(Arc::ptr_eq(&slice, &keep));
+        assert_eq!(slice.len(), 2);
+        assert_eq!(Arc::count(&keep), 2);
+    }
+
+    #[test]
+    fn empty_slice() {
+        let empty = Arc::<[String]>::empty();
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+
+        let clone = empty.clone();
+        assert!(Arc::ptr_eq(&empty, &clone));
+        drop(empty);
+        assert!(clone.is_empty());
+        assert!(clone.is_unique());
+
+        let default: Arc<[u64]> = Arc::default();
+        assert!(default.is_empty());
+        assert_eq!(
+            default.as_ptr() as *const u64 as usize % core::mem::align_of::<u64>(),
+            0
+        );
+    }
+
+    #[test]
+    fn default_unsized() {
+        fn default<T: Default>() -> T {
+            T::default()
+        }
+
+        assert!(default::<Arc<[u8]>>().is_empty());
+        assert!(default::<Arc<str>>().is_empty());
+        assert_eq!(&*default::<Arc<str>>(), "");
+        assert_eq!(*default::<Arc<u32>>(), 0);
+    }
+
+    #[test]
+    fn over_aligned_elements() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(align(128))]
+        struct CacheLine(u8);
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(align(256))]
+        struct BigHeader(u8);
+
+        let items = [CacheLine(1), CacheLine(2), CacheLine(3)];
+        let arcs = [
+            Arc::from_header_and_iter(0u8, items.iter().copied()),
+            Arc::from_header_and_slice(0u8, &items),
+            Arc::from_header_and_slice_cloned(0u8, &items),
+            Arc::from_header_and_vec(0u8, items.to_vec()),
+        ];
+        for arc in &arcs {
+            assert_eq!(arc.slice.as_ptr() as usize % 128, 0);
+            assert_eq!(arc.slice, items);
+        }
+
+        let arc = Arc::from_header_and_slice(BigHeader(7), &items);
+        assert_eq!(&arc.header as *const BigHeader as usize % 256, 0);
+        assert_eq!(arc.slice.as_ptr() as usize % 128, 0);
+        assert_eq!(arc.header, BigHeader(7));
+        assert_eq!(arc.slice, items);
+
+        let arc: Arc<[CacheLine]> = items.iter().copied().collect();
+        assert_eq!(arc.as_ptr() as *const CacheLine as usize % 128, 0);
+        assert_eq!(*arc, items);
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Arc<HeaderSlice<String, [u8]>>>();
+        assert_send_sync::<Arc<HeaderSlice<HeaderWithLength<String>, [Vec<u8>]>>>();
+        assert_send_sync::<crate::ThinArc<String, Vec<u8>>>();
+    }
 }
@@ -21,6 +21,11 @@
 #![allow(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "unstable_dropck_eyepatch", feature(dropck_eyepatch))]
+#![cfg_attr(
+    feature = "nightly",
+    feature(trusted_len, coerce_unsized, dispatch_from_dyn, unsize)
+)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 extern crate alloc;
 #[cfg(feature = "std")]
@@ -28,6 +33,14 @@ extern crate core;
 
 #[cfg(feature = "arc-swap")]
 extern crate arc_swap;
+#[cfg(feature = "borsh")]
+extern crate borsh;
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(loom)]
+extern crate loom;
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
 #[cfg(feature = "serde")]
 extern crate serde;
 #[cfg(feature = "stable_deref_trait")]
@@ -37,23 +50,55 @@ extern crate unsize;
 
 mod arc;
 mod arc_borrow;
+mod arc_slice_builder;
 #[cfg(feature = "arc-swap")]
 mod arc_swap_support;
 mod arc_union;
+mod atomic_arc;
+#[cfg(feature = "borsh")]
+mod borsh_support;
+mod by_address;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_support;
+mod foreign_arc;
 mod header;
 mod iterator_as_exact_size_iterator;
 mod offset_arc;
+mod projected_arc;
+mod raw_arc;
+#[cfg(feature = "rkyv")]
+mod rkyv_support;
+mod sub_arc;
 mod thin_arc;
+mod thin_arc_str;
 mod unique_arc;
+#[cfg(feature = "weak")]
+pub mod weak;
 
 pub use arc::*;
 pub use arc_borrow::*;
+pub use arc_slice_builder::*;
 pub use arc_union::*;
+pub use atomic_arc::*;
+pub use by_address::*;
+pub use foreign_arc::*;
 pub use header::*;
 pub use offset_arc::*;
+pub use projected_arc::*;
+pub use raw_arc::*;
+#[cfg(feature = "rkyv")]
+pub use rkyv_support::*;
+pub use sub_arc::*;
 pub use thin_arc::*;
+pub use thin_arc_str::*;
 pub use unique_arc::*;
 
+// Swapped for their model-checked versions when testing with `--cfg loom`.
+#[cfg(not(loom))]
+use core::{hint, sync::atomic};
+#[cfg(loom)]
+use loom::{hint, sync::atomic};
+
 #[cfg(feature = "std")]
 use std::process::abort;
 
@@ -70,3 +115,97 @@ fn abort() -> ! {
     let _double_panicer = PanicOnDrop;
     panic!();
 }
+
+#[cfg(all(test, feature = "std"))]
+mod test_allocator {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::ptr;
+
+    /// Allocations of at least this many bytes fail, to exercise the error
+    /// paths of fallible constructors.
+    pub(crate) const FAILING_SIZE: usize = 1 << 40;
+
+    struct FailingAllocator;
+
+    unsafe impl GlobalAlloc for FailingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            if layout.size() >= FAILING_SIZE {
+                ptr::null_mut()
+            } else {
+                System.alloc(layout)
+            }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            if layout.size() >= FAILING_SIZE {
+                ptr::null_mut()
+            } else {
+                System.alloc_zeroed(layout)
+            }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: FailingAllocator = FailingAllocator;
+}
+
+#[cfg(test)]
+mod test_drops {
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+    #[derive(Debug, Default)]
+    struct Counts {
+        created: AtomicUsize,
+        dropped: AtomicUsize,
+    }
+
+    /// Creates [`Counted`] values and counts their drops, to check that the
+    /// code under test neither leaks nor double-drops them.
+    #[derive(Default)]
+    pub(crate) struct DropCounter(Arc<Counts>);
+
+    impl DropCounter {
+        pub(crate) fn new() -> Self {
+            Self::default()
+        }
+
+        /// Wraps `value`, counting its creation now and its drop later.
+        pub(crate) fn counted<T>(&self, value: T) -> Counted<T> {
+            self.0.created.fetch_add(1, SeqCst);
+            Counted(value, self.0.clone())
+        }
+
+        /// The number of values dropped so far.
+        pub(crate) fn drops(&self) -> usize {
+            self.0.dropped.load(SeqCst)
+        }
+
+        /// The number of values created, including clones, and not dropped yet.
+        pub(crate) fn live(&self) -> usize {
+            self.0.created.load(SeqCst) - self.drops()
+        }
+    }
+
+    /// A value created by a [`DropCounter`], which counts its drop.
+    #[derive(Debug)]
+    pub(crate) struct Counted<T = ()>(pub(crate) T, Arc<Counts>);
+
+    impl<T: Clone> Clone for Counted<T> {
+        fn clone(&self) -> Self {
+            let value = self.0.clone();
+            self.1.created.fetch_add(1, SeqCst);
+            Counted(value, self.1.clone())
+        }
+    }
+
+    impl<T> Drop for Counted<T> {
+        fn drop(&mut self) {
+            self.1.dropped.fetch_add(1, SeqCst);
+        }
+    }
+}
@@ -4,6 +4,9 @@ use core::mem::ManuallyDrop;
 use core::ops::Deref;
 use core::ptr;
 
+#[cfg(feature = "stable_deref_trait")]
+use stable_deref_trait::{CloneStableDeref, StableDeref};
+
 use super::{Arc, ArcBorrow};
 
 /// An `Arc`, except it holds a pointer to the T instead of to the
@@ -68,6 +71,13 @@ impl<T: fmt::Debug> fmt::Debug for OffsetArc<T> {
     }
 }
 
+/// Prints the address of the data, like the `fmt::Pointer` impl of `Arc`.
+impl<T> fmt::Pointer for OffsetArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Pointer::fmt(&self.ptr, f)
+    }
+}
+
 impl<T: PartialEq> PartialEq for OffsetArc<T> {
     fn eq(&self, other: &OffsetArc<T>) -> bool {
         *(*self) == *(*other)
@@ -79,6 +89,11 @@ impl<T: PartialEq> PartialEq for OffsetArc<T> {
     }
 }
 
+#[cfg(feature = "stable_deref_trait")]
+unsafe impl<T> StableDeref for OffsetArc<T> {}
+#[cfg(feature = "stable_deref_trait")]
+unsafe impl<T> CloneStableDeref for OffsetArc<T> {}
+
 impl<T> OffsetArc<T> {
     /// Temporarily converts |self| into a bonafide Arc and exposes it to the
     /// provided callback. The refcount is not modified.
@@ -95,6 +110,19 @@ impl<T> OffsetArc<T> {
         f(&transient)
     }
 
+    /// Provides mutable access to the contents _if_ the `OffsetArc` is uniquely owned.
+    ///
+    /// This is functionally the same thing as `Arc::get_mut`
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if self.with_arc(|arc| arc.is_unique()) {
+            // Safety: we are the only owner, and `ptr` points to the data of the `ArcInner`
+            unsafe { Some(&mut *self.ptr.as_ptr()) }
+        } else {
+            None
+        }
+    }
+
     /// If uniquely owned, provide a mutable reference
     /// Else create a copy, and mutate that
     ///
@@ -123,7 +151,7 @@ impl<T> OffsetArc<T> {
         }
     }
 
-    /// Clone it as an `Arc`
+    /// Clone it as an `Arc`, incrementing the refcount
     #[inline]
     pub fn clone_arc(&self) -> Arc<T> {
         OffsetArc::with_arc(self, |a| a.clone())
@@ -131,6 +159,9 @@ impl<T> OffsetArc<T> {
 
     /// Produce a pointer to the data that can be converted back
     /// to an `Arc`
+    ///
+    /// The refcount is not modified, so this is a cheap way to pass an
+    /// `OffsetArc` to APIs taking an [`ArcBorrow`].
     #[inline]
     pub fn borrow_arc(&self) -> ArcBorrow<'_, T> {
         ArcBorrow(self.ptr, PhantomData)
@@ -149,3 +180,67 @@ impl<T> OffsetArc<T> {
         Self::with_arc(this, |arc| Arc::strong_count(arc))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, OffsetArc};
+    use alloc::string::String;
+
+    #[test]
+    fn offset_pointer_fmt() {
+        use alloc::format;
+
+        let arc = Arc::new(String::from("a"));
+        let offset = Arc::into_raw_offset(arc.clone());
+        assert_eq!(format!("{:p}", offset), format!("{:p}", offset.clone()));
+        assert_eq!(format!("{:p}", offset), format!("{:p}", arc));
+        let other = Arc::into_raw_offset(Arc::new(String::from("a")));
+        assert_ne!(format!("{:p}", offset), format!("{:p}", other));
+    }
+
+    #[test]
+    fn offset_get_mut() {
+        let mut x: OffsetArc<String> = Arc::into_raw_offset(Arc::new(String::from("a")));
+        x.get_mut().unwrap().push('b');
+        assert_eq!(*x, "ab");
+
+        let y = x.clone();
+        assert!(x.get_mut().is_none());
+        drop(y);
+        assert!(x.get_mut().is_some());
+    }
+
+    #[test]
+    fn offset_make_mut() {
+        let mut x: OffsetArc<String> = Arc::into_raw_offset(Arc::new(String::from("a")));
+        let ptr = x.ptr;
+        x.make_mut().push('b');
+        assert_eq!(x.ptr, ptr);
+
+        let y = x.clone();
+        x.make_mut().push('c');
+        assert_ne!(x.ptr, ptr);
+        assert_eq!(*x, "abc");
+        assert_eq!(*y, "ab");
+        assert_eq!(OffsetArc::strong_count(&x), 1);
+        assert_eq!(OffsetArc::strong_count(&y), 1);
+    }
+
+    #[test]
+    fn offset_borrow_and_clone_arc() {
+        let x: OffsetArc<String> = Arc::into_raw_offset(Arc::new(String::from("a")));
+
+        let borrow = x.borrow_arc();
+        assert_eq!(*borrow, "a");
+        assert_eq!(OffsetArc::strong_count(&x), 1);
+
+        let arc = x.clone_arc();
+        assert_eq!(OffsetArc::strong_count(&x), 2);
+        assert!(core::ptr::eq(&*arc, &*x));
+
+        // Both point at the same data
+        assert!(Arc::ptr_eq(&arc, &borrow.clone_arc()));
+        drop(arc);
+        assert_eq!(OffsetArc::strong_count(&x), 1);
+    }
+}
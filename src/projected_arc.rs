@@ -0,0 +1,161 @@
+use core::fmt;
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+use crate::Arc;
+
+/// An `Arc<T>` projected to a part of its data, such as a field.
+///
+/// This keeps the whole `T` alive, sharing its allocation (and refcount), but
+/// derefs to the `U` it was projected to. Created by [`Arc::project`].
+///
+/// ```
+/// use triomphe::{Arc, ProjectedArc};
+///
+/// struct Config {
+///     name: String,
+///     verbose: bool,
+/// }
+///
+/// let config = Arc::new(Config { name: "app".into(), verbose: true });
+/// let name: ProjectedArc<Config, str> = Arc::project(config, |c| c.name.as_str());
+/// assert_eq!(&*name, "app");
+/// assert!(ProjectedArc::source(&name).verbose);
+/// ```
+pub struct ProjectedArc<T: ?Sized, U: ?Sized> {
+    arc: Arc<T>,
+    // Points into the data of `arc`, or to some `'static` data
+    projected: NonNull<U>,
+}
+
+// Like an `(Arc<T>, &U)`.
+unsafe impl<T: ?Sized + Sync + Send, U: ?Sized + Sync> Send for ProjectedArc<T, U> {}
+unsafe impl<T: ?Sized + Sync + Send, U: ?Sized + Sync> Sync for ProjectedArc<T, U> {}
+
+impl<T: ?Sized> Arc<T> {
+    /// Projects the `Arc` to a part of its data, e.g. a field, without cloning it.
+    ///
+    /// The returned [`ProjectedArc`] owns `this`, so that the whole `T` stays alive.
+    #[inline]
+    pub fn project<U: ?Sized, F>(this: Self, f: F) -> ProjectedArc<T, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let projected = NonNull::from(f(&this));
+        ProjectedArc {
+            arc: this,
+            projected,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> ProjectedArc<T, U> {
+    /// Returns the `Arc` this was projected from.
+    #[inline]
+    pub fn source(this: &Self) -> &Arc<T> {
+        &this.arc
+    }
+
+    /// Gives up the projection, returning the `Arc` it was projected from.
+    #[inline]
+    pub fn into_source(this: Self) -> Arc<T> {
+        this.arc
+    }
+
+    /// Projects further, e.g. to a field of `U`.
+    #[inline]
+    pub fn project<V: ?Sized, F>(this: Self, f: F) -> ProjectedArc<T, V>
+    where
+        F: FnOnce(&U) -> &V,
+    {
+        let projected = NonNull::from(f(&this));
+        ProjectedArc {
+            arc: this.arc,
+            projected,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for ProjectedArc<T, U> {
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &U {
+        // Safety: the closure given to `project` could only return a reference
+        // borrowed from the data of `arc` (which can't move, and is kept alive
+        // by `arc`), or a `'static` one
+        unsafe { self.projected.as_ref() }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Clone for ProjectedArc<T, U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ProjectedArc {
+            arc: self.arc.clone(),
+            projected: self.projected,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Debug> fmt::Debug for ProjectedArc<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Display> fmt::Display for ProjectedArc<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, ProjectedArc};
+    use alloc::string::String;
+
+    #[test]
+    fn project_keeps_source_alive() {
+        struct Big {
+            _a: u64,
+            name: String,
+        }
+
+        let drops = crate::test_drops::DropCounter::new();
+        let big = Arc::new(drops.counted(Big {
+            _a: 1,
+            name: String::from("field"),
+        }));
+        let name_ptr = big.0.name.as_ptr();
+
+        let name = Arc::project(big, |big| &big.0.name);
+        assert_eq!(*name, "field");
+        // Nothing was cloned
+        assert_eq!(name.as_ptr(), name_ptr);
+        assert_eq!(Arc::count(ProjectedArc::source(&name)), 1);
+
+        let clone = name.clone();
+        assert_eq!(Arc::count(ProjectedArc::source(&name)), 2);
+        drop(name);
+        assert_eq!(drops.drops(), 0);
+
+        let bytes = ProjectedArc::project(clone, |name| name.as_bytes());
+        assert_eq!(&*bytes, b"field");
+        assert_eq!(Arc::count(ProjectedArc::source(&bytes)), 1);
+
+        drop(bytes);
+        assert_eq!(drops.drops(), 1);
+    }
+
+    #[test]
+    fn project_into_source() {
+        let arc = Arc::new((1u8, String::from("b")));
+        let second = Arc::project(arc.clone(), |pair| &pair.1);
+        assert_eq!(*second, "b");
+
+        let source = ProjectedArc::into_source(second);
+        assert!(Arc::ptr_eq(&source, &arc));
+        assert_eq!(Arc::count(&arc), 2);
+    }
+}
@@ -0,0 +1,175 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::mem::ManuallyDrop;
+use core::ptr::NonNull;
+
+use crate::{Arc, ArcInner};
+
+/// A non-owning handle to the allocation of an [`Arc`].
+///
+/// A `RawArc` doesn't hold a reference: it doesn't keep the allocation alive,
+/// and nothing tracks whether it still is. It can always be compared by
+/// identity, like [`Arc::ptr_eq`], but turning it back into an `Arc` with
+/// [`upgrade_unchecked`](RawArc::upgrade_unchecked) requires the caller to know
+/// that some `Arc` to the same allocation is still alive.
+///
+/// This is meant for intrusive caches and similar structures, where the liveness
+/// of the entries is guaranteed by other means; otherwise, use `weak::Arc`
+/// (behind the `weak` feature) with its `Weak` references.
+///
+/// Since the allocation may be freed and its address reused, two `RawArc`s
+/// comparing equal only means that they point to the same allocation if both
+/// are known to be alive.
+///
+/// ```
+/// use triomphe::{Arc, RawArc};
+///
+/// let arc = Arc::new(17);
+/// let raw = RawArc::new(&arc);
+/// assert!(raw.is_of(&arc));
+///
+/// // Safety: `arc` is still alive
+/// let upgraded = unsafe { raw.upgrade_unchecked() };
+/// assert!(Arc::ptr_eq(&arc, &upgraded));
+/// ```
+pub struct RawArc<T: ?Sized> {
+    p: NonNull<ArcInner<T>>,
+}
+
+// `upgrade_unchecked` can give an `Arc<T>` on another thread.
+unsafe impl<T: ?Sized + Sync + Send> Send for RawArc<T> {}
+unsafe impl<T: ?Sized + Sync + Send> Sync for RawArc<T> {}
+
+impl<T: ?Sized> RawArc<T> {
+    /// Creates a non-owning handle to the allocation of `arc`.
+    ///
+    /// The refcount is not modified.
+    #[inline]
+    pub fn new(arc: &Arc<T>) -> Self {
+        RawArc { p: arc.p }
+    }
+
+    /// Returns true if the two `RawArc`s point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: Self, other: Self) -> bool {
+        this == other
+    }
+
+    /// Returns true if this `RawArc` points to the allocation of `arc`.
+    #[inline]
+    pub fn is_of(self, arc: &Arc<T>) -> bool {
+        self == RawArc::new(arc)
+    }
+
+    /// Returns the address of the allocation, as with [`Arc::heap_ptr`].
+    #[inline]
+    pub fn heap_ptr(self) -> *const () {
+        self.p.as_ptr() as *const ()
+    }
+
+    /// Creates a new `Arc` to the allocation, incrementing the refcount.
+    ///
+    /// # Safety
+    ///
+    /// The allocation must still be alive, i.e. some `Arc` (or other owning
+    /// pointer, such as a `ThinArc`) to it must exist for the duration of the call.
+    /// There is no way to check this: upgrading a `RawArc` whose `Arc`s have all
+    /// been dropped is a use-after-free.
+    ///
+    /// Since a `RawArc` doesn't count as a reference, the data may also be
+    /// mutably borrowed, e.g. through [`Arc::get_mut`] or a `UniqueArc`: that
+    /// borrow must have ended before calling this.
+    #[inline]
+    pub unsafe fn upgrade_unchecked(self) -> Arc<T> {
+        // Safety: the caller guarantees that the allocation is alive, and we
+        // don't own a reference, so this one must not be dropped
+        let arc = ManuallyDrop::new(Arc::from_inner(self.p));
+        Arc::clone(&arc)
+    }
+}
+
+impl<T: ?Sized> Clone for RawArc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for RawArc<T> {}
+
+impl<T: ?Sized> From<&Arc<T>> for RawArc<T> {
+    #[inline]
+    fn from(arc: &Arc<T>) -> Self {
+        RawArc::new(arc)
+    }
+}
+
+impl<T: ?Sized> PartialEq for RawArc<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // Like `Arc::ptr_eq`, ignore the metadata of fat pointers
+        self.heap_ptr() == other.heap_ptr()
+    }
+}
+
+impl<T: ?Sized> Eq for RawArc<T> {}
+
+impl<T: ?Sized> Hash for RawArc<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.heap_ptr().hash(state)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for RawArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("RawArc").field(&self.heap_ptr()).finish()
+    }
+}
+
+impl<T: ?Sized> fmt::Pointer for RawArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Pointer::fmt(&self.heap_ptr(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RawArc;
+    use crate::Arc;
+    use alloc::string::String;
+
+    #[test]
+    fn raw_identity_and_upgrade() {
+        let arc = Arc::new(String::from("cached"));
+        let raw = RawArc::new(&arc);
+        assert_eq!(Arc::count(&arc), 1);
+
+        assert!(raw.is_of(&arc));
+        assert!(RawArc::ptr_eq(raw, RawArc::from(&arc.clone())));
+        let other = Arc::new(String::from("cached"));
+        assert!(!raw.is_of(&other));
+        assert_ne!(raw, RawArc::new(&other));
+
+        // Safety: `arc` is alive
+        let upgraded = unsafe { raw.upgrade_unchecked() };
+        assert!(Arc::ptr_eq(&arc, &upgraded));
+        assert_eq!(Arc::count(&arc), 2);
+        assert_eq!(*upgraded, "cached");
+
+        drop(upgraded);
+        assert!(arc.is_unique());
+    }
+
+    #[test]
+    fn raw_unsized() {
+        let arc: Arc<[u8]> = Arc::from(&[1u8, 2, 3][..]);
+        let raw = RawArc::new(&arc);
+        assert_eq!(raw.heap_ptr(), arc.heap_ptr() as *const ());
+
+        // Safety: `arc` is alive
+        let upgraded = unsafe { raw.upgrade_unchecked() };
+        assert_eq!(*upgraded, [1, 2, 3]);
+        assert_eq!(Arc::count(&arc), 2);
+    }
+}
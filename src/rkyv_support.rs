@@ -0,0 +1,138 @@
+//! `rkyv` support for `ThinArc`.
+//!
+//! `Arc` is not covered here: `rkyv` implements its traits for `triomphe::Arc`
+//! itself, behind its `triomphe-0_1` feature.
+//!
+//! See the `rkyv` feature in Cargo.toml for its MSRV and inference caveats.
+
+use rkyv::bytecheck::CheckBytes;
+use rkyv::munge::munge;
+use rkyv::rancor::Fallible;
+use rkyv::ser::{Allocator, Writer};
+use rkyv::vec::{ArchivedVec, VecResolver};
+use rkyv::{Archive, Archived, Deserialize, Place, Portable, Serialize};
+
+use crate::{Arc, HeaderWithLength, ThinArc};
+
+/// An archived [`ThinArc`].
+///
+/// The header is stored inline, followed by a relative pointer to the archived slice.
+#[derive(Portable, CheckBytes)]
+#[rkyv(crate = ::rkyv)]
+#[bytecheck(crate = ::rkyv::bytecheck)]
+#[repr(C)]
+pub struct ArchivedThinArc<H, T> {
+    header: H,
+    slice: ArchivedVec<T>,
+}
+
+impl<H, T> ArchivedThinArc<H, T> {
+    /// Returns the archived header.
+    #[inline]
+    pub fn header(&self) -> &H {
+        &self.header
+    }
+
+    /// Returns the archived slice.
+    #[inline]
+    pub fn slice(&self) -> &[T] {
+        self.slice.as_slice()
+    }
+}
+
+/// The resolver for an archived [`ThinArc`].
+pub struct ThinArcResolver<H> {
+    header: H,
+    slice: VecResolver,
+}
+
+impl<H: Archive, T: Archive> Archive for ThinArc<H, T> {
+    type Archived = ArchivedThinArc<Archived<H>, Archived<T>>;
+    type Resolver = ThinArcResolver<H::Resolver>;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedThinArc { header, slice } = out);
+        self.header().resolve(resolver.header, header);
+        ArchivedVec::resolve_from_slice(self.slice(), resolver.slice, slice);
+    }
+}
+
+impl<H, T, S> Serialize<S> for ThinArc<H, T>
+where
+    H: Serialize<S>,
+    T: Serialize<S>,
+    S: Allocator + Writer + Fallible + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(ThinArcResolver {
+            header: self.header().serialize(serializer)?,
+            slice: ArchivedVec::serialize_from_slice(self.slice(), serializer)?,
+        })
+    }
+}
+
+impl<H, T, D> Deserialize<ThinArc<H, T>, D> for ArchivedThinArc<Archived<H>, Archived<T>>
+where
+    H: Archive,
+    T: Archive,
+    Archived<H>: Deserialize<H, D>,
+    Archived<T>: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<ThinArc<H, T>, D::Error> {
+        let header = self.header.deserialize(deserializer)?;
+        let len = self.slice.len();
+        let mut items = self.slice().iter();
+        let arc = Arc::from_header_and_fn(
+            HeaderWithLength {
+                header,
+                length: len,
+            },
+            len,
+            || {
+                // `from_header_and_fn` calls this exactly `len` times
+                items.next().unwrap().deserialize(deserializer)
+            },
+        )?;
+        Ok(Arc::into_thin(arc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ThinArc;
+    use alloc::vec::Vec;
+    use rkyv::rancor::Error;
+
+    // rkyv's relative pointers reach outside of the field they are stored in,
+    // which Stacked Borrows rejects (Tree Borrows accepts them).
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn rkyv_thin_arc() {
+        let thin = ThinArc::from_header_and_slice((), &[1u32, 2, 3, 4]);
+        let bytes = rkyv::to_bytes::<Error>(&thin).unwrap();
+
+        let archived = rkyv::access::<rkyv::Archived<ThinArc<(), u32>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.slice(), &[1, 2, 3, 4]);
+
+        let thin2: ThinArc<(), u32> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(thin2.slice(), thin.slice());
+        assert_eq!(thin2.len(), 4);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn rkyv_thin_arc_header() {
+        let thin = ThinArc::from_header_and_iter(7u64, Vec::<u16>::new().into_iter());
+        let bytes = rkyv::to_bytes::<Error>(&thin).unwrap();
+
+        let archived = rkyv::access::<rkyv::Archived<ThinArc<u64, u16>>, Error>(&bytes).unwrap();
+        assert_eq!(*archived.header(), 7);
+        assert!(archived.slice().is_empty());
+
+        let thin2: ThinArc<u64, u16> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(*thin2.header(), 7);
+        assert!(thin2.is_empty());
+    }
+}
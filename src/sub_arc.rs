@@ -0,0 +1,177 @@
+use core::fmt;
+use core::ops::{Bound, Deref, RangeBounds};
+
+use crate::Arc;
+
+/// A sub-slice of an `Arc<[T]>`, sharing its allocation and refcount.
+///
+/// An `Arc<[T]>` can only point to a whole slice, since its refcount sits right
+/// before the first element. A `SubArc` instead holds the parent `Arc` along with
+/// the range it views, so that slicing never copies the elements.
+///
+/// ```
+/// use triomphe::{Arc, SubArc};
+///
+/// let arc: Arc<[u32]> = Arc::from(&[1, 2, 3, 4, 5][..]);
+/// let sub = SubArc::from(arc.clone()).slice(1..4);
+/// assert_eq!(&*sub, [2, 3, 4]);
+/// assert_eq!(&*sub.slice(1..), [3, 4]);
+/// assert!(Arc::ptr_eq(SubArc::parent(&sub), &arc));
+/// ```
+pub struct SubArc<T> {
+    parent: Arc<[T]>,
+    offset: usize,
+    len: usize,
+}
+
+impl<T> SubArc<T> {
+    /// Narrows this view to `range`, relative to its current start.
+    ///
+    /// The returned `SubArc` shares the parent `Arc`, incrementing its refcount.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds of this view, like slice indexing.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).expect("range start overflows"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).expect("range end overflows"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            start <= end,
+            "range starts at {} but ends at {}",
+            start,
+            end
+        );
+        assert!(
+            end <= self.len,
+            "range end {} out of range for slice of length {}",
+            end,
+            self.len
+        );
+
+        SubArc {
+            parent: self.parent.clone(),
+            offset: self.offset + start,
+            len: end - start,
+        }
+    }
+
+    /// Returns the `Arc` this is a view into.
+    #[inline]
+    pub fn parent(this: &Self) -> &Arc<[T]> {
+        &this.parent
+    }
+
+    /// Gives up the view, returning the `Arc` it was a view into.
+    #[inline]
+    pub fn into_parent(this: Self) -> Arc<[T]> {
+        this.parent
+    }
+}
+
+impl<T> From<Arc<[T]>> for SubArc<T> {
+    /// Views the whole slice.
+    #[inline]
+    fn from(parent: Arc<[T]>) -> Self {
+        SubArc {
+            len: parent.len(),
+            parent,
+            offset: 0,
+        }
+    }
+}
+
+impl<T> Deref for SubArc<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        // Safety: the range was checked to be in bounds when creating `self`
+        unsafe {
+            self.parent
+                .get_unchecked(self.offset..self.offset + self.len)
+        }
+    }
+}
+
+impl<T> Clone for SubArc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        SubArc {
+            parent: self.parent.clone(),
+            offset: self.offset,
+            len: self.len,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for SubArc<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq> Eq for SubArc<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for SubArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, SubArc};
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn sub_arc_slicing() {
+        let arc: Arc<[u32]> = Arc::from(&[0, 1, 2, 3, 4, 5, 6, 7][..]);
+        let whole = SubArc::from(arc.clone());
+        assert_eq!(&*whole, &*arc);
+
+        let middle = whole.slice(2..6);
+        assert_eq!(&*middle, [2, 3, 4, 5]);
+        assert_eq!(middle.as_ptr(), arc[2..].as_ptr());
+
+        // Ranges are relative to the view
+        assert_eq!(&*middle.slice(1..=2), [3, 4]);
+        assert_eq!(&*middle.slice(..1), [2]);
+        assert_eq!(&*middle.slice(3..), [5]);
+        assert!(middle.slice(4..).is_empty());
+        assert_eq!(middle.slice(1..3), whole.slice(3..5));
+    }
+
+    #[test]
+    fn sub_arc_keeps_parent_alive() {
+        let arc: Arc<[String]> = (0..4).map(|i| i.to_string()).collect::<Vec<_>>().into();
+        let sub = SubArc::from(arc.clone()).slice(1..3);
+        assert_eq!(Arc::count(&arc), 2);
+
+        drop(arc);
+        assert_eq!(&*sub, ["1", "2"]);
+
+        let narrower = sub.slice(1..);
+        drop(sub);
+        assert_eq!(&*narrower, ["2"]);
+        assert_eq!(Arc::count(SubArc::parent(&narrower)), 1);
+
+        let parent = SubArc::into_parent(narrower);
+        assert_eq!(parent.len(), 4);
+    }
+
+    #[test]
+    #[should_panic = "range end 5 out of range for slice of length 4"]
+    fn sub_arc_out_of_bounds() {
+        let arc: Arc<[u8]> = Arc::from(&[0u8; 8][..]);
+        let _ = SubArc::from(arc).slice(2..6).slice(1..5);
+    }
+}
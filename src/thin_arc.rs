@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use core::cmp::Ordering;
 use core::ffi::c_void;
 use core::fmt;
@@ -5,9 +6,18 @@ use core::hash::{Hash, Hasher};
 use core::iter::{ExactSizeIterator, Iterator};
 use core::marker::PhantomData;
 use core::mem::ManuallyDrop;
-use core::ops::Deref;
+use core::ops::{Deref, Index};
 use core::ptr;
+use core::slice::{self, SliceIndex};
+use core::str::{self, Utf8Error};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "stable_deref_trait")]
+use stable_deref_trait::{CloneStableDeref, StableDeref};
+
+#[cfg(feature = "serde")]
+use super::{deserialize_header_slice, serialize_header_slice};
 use super::{Arc, ArcInner, HeaderSliceWithLength, HeaderWithLength};
 
 /// A "thin" `Arc` containing dynamically sized data
@@ -37,6 +47,11 @@ unsafe impl<H: Sync + Send, T: Sync + Send> Sync for ThinArc<H, T> {}
 // Synthesize a fat pointer from a thin pointer.
 //
 // See the comment around the analogous operation in from_header_and_iter.
+//
+// `ArcInner` and `HeaderSlice` are `repr(C)`, and `[T; 0]` has the alignment of
+// `T`, so the fields of both types are at the same offsets: in particular, the
+// padding between the header and the slice, needed for over-aligned `T`, is the
+// same one as the allocation was laid out with.
 #[inline]
 fn thin_to_thick<H, T>(
     thin: *mut ArcInner<HeaderSliceWithLength<H, [T; 0]>>,
@@ -57,10 +72,9 @@ impl<H, T> ThinArc<H, T> {
     {
         // Synthesize transient Arc, which never touches the refcount of the ArcInner.
         let transient = unsafe {
-            ManuallyDrop::new(Arc {
-                p: ptr::NonNull::new_unchecked(thin_to_thick(self.ptr.as_ptr())),
-                phantom: PhantomData,
-            })
+            ManuallyDrop::new(Arc::from_inner(ptr::NonNull::new_unchecked(thin_to_thick(
+                self.ptr.as_ptr(),
+            ))))
         };
 
         // Expose the transient Arc to the callback, which may clone it if it wants
@@ -77,10 +91,9 @@ impl<H, T> ThinArc<H, T> {
     {
         // Synthesize transient Arc, which never touches the refcount of the ArcInner.
         let mut transient = unsafe {
-            ManuallyDrop::new(Arc {
-                p: ptr::NonNull::new_unchecked(thin_to_thick(self.ptr.as_ptr())),
-                phantom: PhantomData,
-            })
+            ManuallyDrop::new(Arc::from_inner(ptr::NonNull::new_unchecked(thin_to_thick(
+                self.ptr.as_ptr(),
+            ))))
         };
 
         // Expose the transient Arc to the callback, which may clone it if it wants
@@ -90,12 +103,13 @@ impl<H, T> ThinArc<H, T> {
 
     /// Creates a `ThinArc` for a HeaderSlice using the given header struct and
     /// iterator to generate the slice.
+    ///
+    /// If the iterator panics, the header and the elements yielded so far are dropped.
     pub fn from_header_and_iter<I>(header: H, items: I) -> Self
     where
         I: Iterator<Item = T> + ExactSizeIterator,
     {
-        let header = HeaderWithLength::new(header, items.len());
-        Arc::into_thin(Arc::from_header_and_iter(header, items))
+        Arc::into_thin(Arc::from_header_with_length_and_iter(header, items))
     }
 
     /// Creates a `ThinArc` for a HeaderSlice using the given header struct and
@@ -104,8 +118,49 @@ impl<H, T> ThinArc<H, T> {
     where
         T: Copy,
     {
-        let header = HeaderWithLength::new(header, items.len());
-        Arc::into_thin(Arc::from_header_and_slice(header, items))
+        Arc::into_thin(Arc::from_header_with_length_and_slice(header, items))
+    }
+
+    /// Returns a reference to the header, without the inline length.
+    #[inline]
+    pub fn header(&self) -> &H {
+        &self.deref().header.header
+    }
+
+    /// Returns a reference to the slice.
+    #[inline]
+    pub fn slice(&self) -> &[T] {
+        &self.deref().slice
+    }
+
+    /// Returns the number of elements in the slice.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.deref().header.length
+    }
+
+    /// Returns `true` if the slice has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Provides mutable access to the header and the slice _if_ the `ThinArc`
+    /// is uniquely owned.
+    ///
+    /// The inline length is not exposed, so that it always matches the slice.
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<(&mut H, &mut [T])> {
+        if Self::with_arc(this, |arc| arc.is_unique()) {
+            unsafe {
+                // Safety: we are the only owner, and the fat pointer is
+                //         synthesized from the (correct) inline length
+                let inner = thin_to_thick(this.ptr.as_ptr());
+                Some((&mut (*inner).data.header.header, &mut (*inner).data.slice))
+            }
+        } else {
+            None
+        }
     }
 
     /// Returns the address on the heap of the ThinArc itself -- not the T
@@ -129,7 +184,8 @@ impl<H, T> ThinArc<H, T> {
     /// The raw pointer must have been previously returned by a call to
     /// ThinArc::into_raw.
     ///
-    /// The user of from_raw has to make sure a specific value of T is only dropped once.
+    /// The user of from_raw has to make sure a specific value of T is only dropped once,
+    /// that is, each call to `from_raw` must be paired with exactly one call to `into_raw`.
     ///
     /// This function is unsafe because improper use may lead to memory unsafety,
     /// even if the returned ThinArc is never accessed.
@@ -142,6 +198,9 @@ impl<H, T> ThinArc<H, T> {
     }
 
     /// Consume ThinArc and returned the wrapped pointer.
+    ///
+    /// The pointer is a single word and the refcount is not modified;
+    /// use [`ThinArc::from_raw`] to get the `ThinArc` back.
     #[inline]
     pub fn into_raw(self) -> *const c_void {
         let this = ManuallyDrop::new(self);
@@ -156,6 +215,15 @@ impl<H, T> ThinArc<H, T> {
         self.ptr()
     }
 
+    /// Returns `true` if the two `ThinArc`s point to the same allocation.
+    ///
+    /// Since every allocation also holds the reference count, distinct
+    /// allocations never share an address, even for zero-sized `H` and `T`.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.ptr == other.ptr
+    }
+
     /// The reference count of this `Arc`.
     ///
     /// The number does not include borrowed pointers,
@@ -168,6 +236,52 @@ impl<H, T> ThinArc<H, T> {
     pub fn strong_count(this: &Self) -> usize {
         Self::with_arc(this, Arc::strong_count)
     }
+
+    /// Converts this `ThinArc` into a fat `Arc`, whose slice length is recovered
+    /// from the inline length.
+    ///
+    /// Same as [`Arc::from_thin`]: this neither allocates nor modifies the refcount.
+    #[inline]
+    pub fn into_arc(self) -> Arc<HeaderSliceWithLength<H, [T]>> {
+        Arc::from_thin(self)
+    }
+
+    /// Converts a fat `Arc` into a `ThinArc`.
+    ///
+    /// Same as [`Arc::into_thin`]: this neither allocates nor modifies the refcount.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inline length doesn't match the length of the slice.
+    #[inline]
+    pub fn from_arc(arc: Arc<HeaderSliceWithLength<H, [T]>>) -> Self {
+        Arc::into_thin(arc)
+    }
+}
+
+impl<H> ThinArc<H, u8> {
+    /// Creates a `ThinArc` storing the UTF-8 bytes of `string`, with the given header.
+    ///
+    /// ```
+    /// use triomphe::ThinArc;
+    ///
+    /// let thin = ThinArc::from_header_and_str(1u8, "héllo");
+    /// assert_eq!(thin.as_str(), Ok("héllo"));
+    /// assert_eq!(thin.len(), "héllo".len());
+    /// ```
+    pub fn from_header_and_str(header: H, string: &str) -> Self {
+        Self::from_header_and_slice(header, string.as_bytes())
+    }
+
+    /// Returns the bytes of the slice as a string slice.
+    ///
+    /// A `ThinArc<H, u8>` can hold arbitrary bytes, so this fails if they are not
+    /// valid UTF-8. That can't happen for `ThinArc`s created by
+    /// [`from_header_and_str`](Self::from_header_and_str).
+    #[inline]
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        str::from_utf8(self.slice())
+    }
 }
 
 impl<H, T> Deref for ThinArc<H, T> {
@@ -179,6 +293,37 @@ impl<H, T> Deref for ThinArc<H, T> {
     }
 }
 
+impl<H, T, I: SliceIndex<[T]>> Index<I> for ThinArc<H, T> {
+    type Output = I::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        &self.slice()[index]
+    }
+}
+
+impl<'a, H, T> IntoIterator for &'a ThinArc<H, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.slice().iter()
+    }
+}
+
+/// Borrows the slice, ignoring the header.
+///
+/// There is deliberately no `Borrow<[T]>` impl: the `Eq` and `Hash` impls of
+/// `ThinArc` take the header and length into account, so lookups by slice in a
+/// collection keyed by `ThinArc` would silently miss.
+impl<H, T> AsRef<[T]> for ThinArc<H, T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.slice()
+    }
+}
+
 impl<H, T> Clone for ThinArc<H, T> {
     #[inline]
     fn clone(&self) -> Self {
@@ -200,6 +345,70 @@ impl<H, T> Drop for ThinArc<H, T> {
 }
 
 impl<H, T> Arc<HeaderSliceWithLength<H, [T]>> {
+    /// Creates an Arc for a HeaderSlice using the given header struct and
+    /// iterator to generate the slice, storing the length of the slice in a
+    /// [`HeaderWithLength`]. The length is taken from the iterator, so the
+    /// resulting Arc can always be converted into a `ThinArc`.
+    ///
+    /// If the iterator panics, the header and the elements yielded so far are dropped.
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let arc = Arc::from_header_with_length_and_iter("header", 0..3);
+    /// assert_eq!(arc.header.length(), 3);
+    /// let thin = Arc::into_thin(arc);
+    /// assert_eq!(thin.slice(), [0, 1, 2]);
+    /// ```
+    pub fn from_header_with_length_and_iter<I>(header: H, items: I) -> Self
+    where
+        I: Iterator<Item = T> + ExactSizeIterator,
+    {
+        let header = HeaderWithLength {
+            header,
+            length: items.len(),
+        };
+        Arc::from_header_and_iter(header, items)
+    }
+
+    /// Creates an Arc for a HeaderSlice using the given header struct and
+    /// a slice to copy, storing the length of the slice in a [`HeaderWithLength`].
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let arc = Arc::from_header_with_length_and_slice("header", &[1, 2, 3]);
+    /// assert_eq!(arc.header.length(), 3);
+    /// ```
+    pub fn from_header_with_length_and_slice(header: H, items: &[T]) -> Self
+    where
+        T: Copy,
+    {
+        let header = HeaderWithLength {
+            header,
+            length: items.len(),
+        };
+        Arc::from_header_and_slice(header, items)
+    }
+
+    /// Creates an Arc for a HeaderSlice using the given header struct and
+    /// a vec to move the elements from, storing the length of the slice in a
+    /// [`HeaderWithLength`].
+    ///
+    /// ```
+    /// use triomphe::Arc;
+    ///
+    /// let arc = Arc::from_header_with_length_and_vec("header", vec![String::new()]);
+    /// assert_eq!(arc.header.length(), 1);
+    /// ```
+    pub fn from_header_with_length_and_vec(header: H, items: Vec<T>) -> Self {
+        let header = HeaderWithLength {
+            header,
+            length: items.len(),
+        };
+        Arc::from_header_and_vec(header, items)
+    }
+
     /// Converts an `Arc` into a `ThinArc`. This consumes the `Arc`, so the refcount
     /// is not modified.
     ///
@@ -243,12 +452,73 @@ impl<H, T> Arc<HeaderSliceWithLength<H, [T]>> {
     pub fn from_thin(a: ThinArc<H, T>) -> Self {
         let a = ManuallyDrop::new(a);
         let ptr = thin_to_thick(a.ptr.as_ptr());
-        unsafe {
-            Arc {
-                p: ptr::NonNull::new_unchecked(ptr),
-                phantom: PhantomData,
-            }
-        }
+        unsafe { Arc::from_inner(ptr::NonNull::new_unchecked(ptr)) }
+    }
+
+    /// Borrows this `Arc` as a `ThinArc`, e.g. to pass it to an API expecting
+    /// a `&ThinArc`. The refcount is not modified.
+    #[inline]
+    pub fn borrow_thin(&self) -> ThinArcBorrow<'_, H, T> {
+        assert_eq!(
+            self.header.length,
+            self.slice.len(),
+            "Length needs to be correct for ThinArc to work"
+        );
+        // Safety: the length was just checked, and the `ManuallyDrop` keeps the
+        // copied `Arc` from releasing the reference owned by `self`
+        let thin = unsafe { Self::into_thin_unchecked(ptr::read(self)) };
+        ThinArcBorrow(ManuallyDrop::new(thin), PhantomData)
+    }
+}
+
+impl<H, T> ThinArc<H, T> {
+    /// Borrows this `ThinArc` as a fat `Arc`, e.g. to pass it to an API expecting
+    /// a `&Arc<HeaderSliceWithLength<H, [T]>>`. The refcount is not modified.
+    ///
+    /// See also [`with_arc`](ThinArc::with_arc).
+    #[inline]
+    pub fn borrow_fat(&self) -> FatArcBorrow<'_, H, T> {
+        // Safety: the `ManuallyDrop` keeps the copied `ThinArc` from releasing
+        // the reference owned by `self`
+        let fat = Arc::from_thin(unsafe { ptr::read(self) });
+        FatArcBorrow(ManuallyDrop::new(fat), PhantomData)
+    }
+}
+
+/// A `ThinArc` borrowed from an `Arc<HeaderSliceWithLength<H, [T]>>`, created
+/// by [`Arc::borrow_thin`].
+///
+/// This derefs to a `ThinArc` sharing the refcount of the original `Arc`, but
+/// which doesn't own a reference.
+pub struct ThinArcBorrow<'a, H, T>(
+    ManuallyDrop<ThinArc<H, T>>,
+    PhantomData<&'a Arc<HeaderSliceWithLength<H, [T]>>>,
+);
+
+impl<'a, H, T> Deref for ThinArcBorrow<'a, H, T> {
+    type Target = ThinArc<H, T>;
+
+    #[inline]
+    fn deref(&self) -> &ThinArc<H, T> {
+        &self.0
+    }
+}
+
+/// A fat `Arc` borrowed from a `ThinArc<H, T>`, created by [`ThinArc::borrow_fat`].
+///
+/// This derefs to an `Arc` sharing the refcount of the original `ThinArc`, but
+/// which doesn't own a reference.
+pub struct FatArcBorrow<'a, H, T>(
+    ManuallyDrop<Arc<HeaderSliceWithLength<H, [T]>>>,
+    PhantomData<&'a ThinArc<H, T>>,
+);
+
+impl<'a, H, T> Deref for FatArcBorrow<'a, H, T> {
+    type Target = Arc<HeaderSliceWithLength<H, [T]>>;
+
+    #[inline]
+    fn deref(&self) -> &Arc<HeaderSliceWithLength<H, [T]>> {
+        &self.0
     }
 }
 
@@ -287,12 +557,43 @@ impl<H: fmt::Debug, T: fmt::Debug> fmt::Debug for ThinArc<H, T> {
     }
 }
 
+/// Prints the thin pointer, as returned by [`ThinArc::ptr`]: clones print the
+/// same address, consistently with [`ThinArc::ptr_eq`].
 impl<H, T> fmt::Pointer for ThinArc<H, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Pointer::fmt(&self.ptr(), f)
     }
 }
 
+#[cfg(feature = "stable_deref_trait")]
+unsafe impl<H, T> StableDeref for ThinArc<H, T> {}
+#[cfg(feature = "stable_deref_trait")]
+unsafe impl<H, T> CloneStableDeref for ThinArc<H, T> {}
+
+/// Deserialized in the same format as `Arc<HeaderSlice<H, [T]>>`.
+#[cfg(feature = "serde")]
+impl<'de, H: Deserialize<'de>, T: Deserialize<'de>> Deserialize<'de> for ThinArc<H, T> {
+    fn deserialize<D>(deserializer: D) -> Result<ThinArc<H, T>, D::Error>
+    where
+        D: ::serde::de::Deserializer<'de>,
+    {
+        let (header, slice): (H, Vec<T>) = deserialize_header_slice(deserializer)?;
+        Ok(ThinArc::from_header_and_iter(header, slice.into_iter()))
+    }
+}
+
+/// Serialized in the same format as `Arc<HeaderSlice<H, [T]>>`: the inline length
+/// is only serialized as part of the slice.
+#[cfg(feature = "serde")]
+impl<H: Serialize, T: Serialize> Serialize for ThinArc<H, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::ser::Serializer,
+    {
+        serialize_header_slice(serializer, self.header(), self.slice())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Arc, HeaderWithLength, ThinArc};
@@ -315,8 +616,7 @@ mod tests {
 
     #[test]
     fn empty_thin() {
-        let header = HeaderWithLength::new(100u32, 0);
-        let x = Arc::from_header_and_iter(header, core::iter::empty::<i32>());
+        let x = Arc::from_header_with_length_and_iter(100u32, core::iter::empty::<i32>());
         let y = Arc::into_thin(x.clone());
         assert_eq!(y.header.header, 100);
         assert!(y.slice.is_empty());
@@ -324,6 +624,74 @@ mod tests {
         assert!(x.slice.is_empty());
     }
 
+    #[test]
+    fn thin_from_header_and_slice() {
+        assert_eq!(
+            core::mem::size_of::<ThinArc<u32, u8>>(),
+            core::mem::size_of::<usize>()
+        );
+        assert_eq!(
+            core::mem::size_of::<Option<ThinArc<u32, u8>>>(),
+            core::mem::size_of::<usize>()
+        );
+
+        let a = ThinArc::from_header_and_slice(7u32, b"interned");
+        assert_eq!(a.header.header, 7);
+        assert_eq!(a.header.length, 8);
+        assert_eq!(&a.slice, b"interned");
+
+        let empty = ThinArc::from_header_and_slice(7u32, &[0u8; 0]);
+        assert_eq!(empty.header.header, 7);
+        assert_eq!(empty.header.length, 0);
+        assert!(empty.slice.is_empty());
+    }
+
+    #[test]
+    fn thin_accessors() {
+        let a = ThinArc::from_header_and_iter("meta", [1u16, 2, 3].iter().copied());
+        assert_eq!(*a.header(), "meta");
+        assert_eq!(a.slice(), [1, 2, 3]);
+        assert_eq!(a.len(), 3);
+        assert!(!a.is_empty());
+
+        let empty = ThinArc::from_header_and_iter("meta", core::iter::empty::<u16>());
+        assert_eq!(*empty.header(), "meta");
+        assert!(empty.slice().is_empty());
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn thin_index_and_iter() {
+        let a: ThinArc<(), u32> = ThinArc::from_header_and_slice((), &[1, 2, 3, 4]);
+
+        let mut sum = 0;
+        for x in &a {
+            sum += x;
+        }
+        assert_eq!(sum, 10);
+
+        assert_eq!(a[2], 3);
+        assert_eq!(a[1..], [2, 3, 4]);
+        assert_eq!(a[..2], [1, 2]);
+    }
+
+    #[test]
+    #[allow(clippy::redundant_clone)]
+    fn thin_ptr_eq() {
+        let a = ThinArc::from_header_and_slice((), &[(); 3]);
+        let b = ThinArc::from_header_and_slice((), &[(); 3]);
+        assert_eq!(a, b);
+        assert!(!ThinArc::ptr_eq(&a, &b));
+        assert!(ThinArc::ptr_eq(&a, &a.clone()));
+
+        let a = ThinArc::from_header_and_slice(1u8, &[2u16, 3]);
+        let b = ThinArc::from_header_and_slice(1u8, &[2u16, 3]);
+        assert_eq!(a, b);
+        assert!(!ThinArc::ptr_eq(&a, &b));
+        assert!(ThinArc::ptr_eq(&a, &a.clone()));
+    }
+
     #[test]
     fn thin_assert_padding() {
         #[derive(Clone, Default)]
@@ -333,9 +701,8 @@ mod tests {
         }
 
         // The header will have more alignment than `Padded`
-        let header = HeaderWithLength::new(0i32, 2);
         let items = vec![Padded { i: 0xdead }, Padded { i: 0xbeef }];
-        let a = ThinArc::from_header_and_iter(header, items.into_iter());
+        let a = ThinArc::from_header_and_iter(0i32, items.into_iter());
         assert_eq!(a.slice.len(), 2);
         assert_eq!(a.slice[0].i, 0xdead);
         assert_eq!(a.slice[1].i, 0xbeef);
@@ -347,9 +714,8 @@ mod tests {
         let mut canary = atomic::AtomicUsize::new(0);
         let c = Canary(&mut canary as *mut atomic::AtomicUsize);
         let v = vec![5, 6];
-        let header = HeaderWithLength::new(c, v.len());
         {
-            let x = Arc::into_thin(Arc::from_header_and_slice(header, &v));
+            let x = Arc::into_thin(Arc::from_header_with_length_and_slice(c, &v));
             let y = ThinArc::with_arc(&x, |q| q.clone());
             let _ = y.clone();
             let _ = x == x;
@@ -364,9 +730,8 @@ mod tests {
         let mut canary = atomic::AtomicUsize::new(0);
         let c = Canary(&mut canary as *mut atomic::AtomicUsize);
         let v = vec![5, 6];
-        let header = HeaderWithLength::new(c, v.len());
         {
-            let x = Arc::into_thin(Arc::from_header_and_iter(header, v.into_iter()));
+            let x = Arc::into_thin(Arc::from_header_with_length_and_iter(c, v.into_iter()));
             let y = ThinArc::with_arc(&x, |q| q.clone());
             let _ = y.clone();
             let _ = x == x;
@@ -380,10 +745,10 @@ mod tests {
         let mut canary = atomic::AtomicUsize::new(0);
         let c = Canary(&mut canary as *mut atomic::AtomicUsize);
         let v = vec![5, 6];
-        let header = HeaderWithLength::new(c, v.len());
         {
             type ThinArcCanary = ThinArc<Canary, u32>;
-            let x: ThinArcCanary = Arc::into_thin(Arc::from_header_and_iter(header, v.into_iter()));
+            let x: ThinArcCanary =
+                Arc::into_thin(Arc::from_header_with_length_and_iter(c, v.into_iter()));
             let ptr = x.as_ptr();
 
             assert_eq!(x.into_raw(), ptr);
@@ -393,6 +758,23 @@ mod tests {
         assert_eq!(canary.load(Acquire), 1);
     }
 
+    #[test]
+    fn into_raw_and_from_raw_refcount() {
+        let x = ThinArc::from_header_and_slice(17u8, b"ffi");
+        let y = x.clone();
+        assert_eq!(ThinArc::strong_count(&y), 2);
+
+        let ptr = x.into_raw();
+        assert_eq!(ptr, y.as_ptr());
+        assert_eq!(ThinArc::strong_count(&y), 2);
+
+        let x = unsafe { ThinArc::<u8, u8>::from_raw(ptr) };
+        assert_eq!(ThinArc::strong_count(&y), 2);
+        assert!(ThinArc::ptr_eq(&x, &y));
+        assert_eq!(x.header.header, 17);
+        assert_eq!(&x.slice, b"ffi");
+    }
+
     #[test]
     fn thin_eq_and_cmp() {
         [
@@ -469,6 +851,40 @@ mod tests {
         })
     }
 
+    #[test]
+    fn thin_get_mut() {
+        let mut a = ThinArc::from_header_and_slice(1u8, &[1u16, 2, 3]);
+        {
+            let (header, slice) = ThinArc::get_mut(&mut a).unwrap();
+            *header = 5;
+            slice[1] = 20;
+        }
+        assert_eq!(*a.header(), 5);
+        assert_eq!(a.slice(), [1, 20, 3]);
+        assert_eq!(a.len(), 3);
+
+        let b = a.clone();
+        assert!(ThinArc::get_mut(&mut a).is_none());
+        drop(b);
+        assert!(ThinArc::get_mut(&mut a).is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn thin_as_ref() {
+        use alloc::boxed::Box;
+        use std::collections::HashMap;
+
+        let thin: ThinArc<(), u8> = ThinArc::from_header_and_slice((), b"key");
+
+        let mut map: HashMap<Box<[u8]>, u32> = HashMap::new();
+        map.insert(Box::from(&b"key"[..]), 42);
+        assert_eq!(map.get(thin.as_ref()), Some(&42));
+
+        let slice: &[u8] = thin.as_ref();
+        assert_eq!(slice, b"key");
+    }
+
     #[test]
     fn with_arc_mut() {
         let mut arc: ThinArc<u8, u16> = ThinArc::from_header_and_slice(1u8, &[1, 2, 3]);
@@ -510,4 +926,254 @@ mod tests {
     const _: () = is_partial_ord::<ThinArc<f64, u64>>();
     const _: () = is_partial_ord::<ThinArc<u64, f64>>();
     const _: () = is_ord::<ThinArc<u64, u64>>();
+
+    #[test]
+    #[cfg(feature = "stable_deref_trait")]
+    fn thin_stable_deref() {
+        use stable_deref_trait::StableDeref;
+
+        /// A minimal `owning_ref`: a borrow of `owner`'s target that lives alongside it.
+        struct OwningRef<O, T: ?Sized + 'static> {
+            _owner: O,
+            reference: &'static T,
+        }
+
+        impl<O: StableDeref, T: ?Sized> OwningRef<O, T> {
+            fn new(owner: O, f: impl FnOnce(&O::Target) -> &T) -> Self {
+                // Safety: the target doesn't move when `owner` does, and the
+                // reference is never handed out for longer than `owner` lives
+                let reference = unsafe { &*(f(&owner) as *const T) };
+                OwningRef {
+                    _owner: owner,
+                    reference,
+                }
+            }
+
+            fn get(&self) -> &T {
+                self.reference
+            }
+        }
+
+        let thin = ThinArc::from_header_and_slice(1u8, &[1u32, 2, 3]);
+        let owning_ref = OwningRef::new(thin, |x| &x.slice[1..]);
+        let moved = alloc::boxed::Box::new(owning_ref);
+        assert_eq!(moved.get(), &[2, 3]);
+    }
+
+    #[test]
+    fn thin_over_aligned_elements() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(align(128))]
+        struct CacheLine(u8);
+
+        let items = [CacheLine(1), CacheLine(2)];
+        let thin = ThinArc::from_header_and_slice(3u8, &items);
+        assert_eq!(thin.slice().as_ptr() as usize % 128, 0);
+        assert_eq!(*thin.header(), 3);
+        assert_eq!(thin.slice(), items);
+
+        let arc = Arc::from_thin(thin.clone());
+        assert_eq!(arc.slice.as_ptr(), thin.slice().as_ptr());
+        assert_eq!(arc.header.length, 2);
+    }
+
+    #[test]
+    fn thin_from_header_and_str() {
+        for string in ["", "ascii", "héllo wörld", "日本語", "🦀 crab"] {
+            let thin = ThinArc::from_header_and_str(7u16, string);
+            assert_eq!(*thin.header(), 7);
+            assert_eq!(thin.len(), string.len());
+            assert_eq!(thin.slice(), string.as_bytes());
+            assert_eq!(thin.as_str(), Ok(string));
+        }
+
+        let empty = ThinArc::from_header_and_str((), "");
+        assert!(empty.is_empty());
+        assert_eq!(empty.as_str(), Ok(""));
+
+        let invalid = ThinArc::from_header_and_slice((), &[0xffu8, 0xfe]);
+        assert!(invalid.as_str().is_err());
+    }
+
+    #[test]
+    fn thin_into_arc_round_trip() {
+        let thin = ThinArc::from_header_and_slice(7u8, &[1u32, 2, 3]);
+        let keep = thin.clone();
+        let ptr = thin.slice().as_ptr();
+
+        let arc = thin.into_arc();
+        assert_eq!(arc.slice.len(), 3);
+        assert_eq!(arc.slice.as_ptr(), ptr);
+        assert_eq!(arc.header.length, 3);
+        assert_eq!(Arc::count(&arc), 2);
+
+        let thin = ThinArc::from_arc(arc);
+        assert_eq!(thin, keep);
+        assert_eq!(thin.ptr(), keep.ptr());
+        assert_eq!(ThinArc::strong_count(&keep), 2);
+    }
+
+    #[test]
+    #[should_panic = "Length needs to be correct for ThinArc to work"]
+    #[allow(deprecated)]
+    fn thin_from_arc_wrong_length() {
+        let arc = Arc::from_header_and_slice(HeaderWithLength::new(0u8, 2), &[1u32, 2, 3]);
+        let _ = ThinArc::from_arc(arc);
+    }
+
+    #[test]
+    fn thin_simd_elements() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(align(32))]
+        struct F32x8([f32; 8]);
+
+        fn check<H: Copy + PartialEq + core::fmt::Debug>(header: H) {
+            for len in [0usize, 1, 3].iter().copied() {
+                let lanes: alloc::vec::Vec<_> = (0..len).map(|i| F32x8([i as f32; 8])).collect();
+                let thin = ThinArc::from_header_and_slice(header, &lanes);
+                let from_iter = ThinArc::from_header_and_iter(header, lanes.iter().copied());
+
+                for thin in [thin.clone(), from_iter, thin].iter() {
+                    assert_eq!(thin.slice().as_ptr() as usize % 32, 0);
+                    assert_eq!(*thin.header(), header);
+                    assert_eq!(thin.slice(), &lanes[..]);
+                    // Going through the fat `Arc` finds the same slice
+                    assert_eq!(
+                        thin.with_arc(|arc| arc.slice.as_ptr()),
+                        thin.slice().as_ptr()
+                    );
+                }
+            }
+        }
+
+        check(());
+        check(1u8);
+        check([2u64; 3]);
+    }
+
+    #[test]
+    fn thin_pointer_fmt() {
+        use alloc::format;
+
+        let a = ThinArc::from_header_and_slice(0u8, &[1u32, 2]);
+        let b = ThinArc::from_header_and_slice(0u8, &[1u32, 2]);
+        assert_eq!(format!("{:p}", a), format!("{:p}", a.clone()));
+        assert_eq!(format!("{:p}", a), format!("{:p}", a.ptr()));
+        assert_ne!(format!("{:p}", a), format!("{:p}", b));
+    }
+
+    #[test]
+    fn thin_length_is_consistent() {
+        for len in [0usize, 1, 5].iter().copied() {
+            let arc = Arc::from_header_with_length_and_iter('h', (0..len).map(|i| i as u32));
+            assert_eq!(arc.header.length(), len);
+            assert_eq!(arc.slice.len(), len);
+
+            let thin = Arc::into_thin(arc);
+            assert_eq!(thin.len(), len);
+            assert_eq!(thin.with_arc(|arc| arc.header.length()), len);
+
+            let thin = ThinArc::from_header_and_iter('h', 0..len);
+            assert_eq!(thin.with_arc(|arc| arc.header.length()), len);
+            let thin = ThinArc::from_header_and_slice('h', &[0u8; 5][..len]);
+            assert_eq!(thin.with_arc(|arc| arc.header.length()), len);
+
+            let arc = Arc::from_header_with_length_and_slice('h', &[0u8; 5][..len]);
+            assert_eq!(arc.header.length(), len);
+            assert_eq!(Arc::into_thin(arc).len(), len);
+            let arc =
+                Arc::from_header_with_length_and_vec('h', vec![alloc::string::String::new(); len]);
+            assert_eq!(arc.header.length(), len);
+            assert_eq!(Arc::into_thin(arc).len(), len);
+        }
+    }
+
+    #[test]
+    fn thin_zst_header_refcount() {
+        for len in [0, 1, 2, 7, 8, 9, 64] {
+            let bytes: alloc::vec::Vec<u8> = (0..len as u8).collect();
+            let thin = ThinArc::from_header_and_slice((), &bytes);
+            assert_eq!(thin.len(), len);
+            assert_eq!(thin.header.length, len);
+            // The fat `Arc` rebuilt from the thin pointer finds the same refcount
+            assert_eq!(thin.with_arc(|arc| arc.heap_ptr()), thin.heap_ptr());
+            assert_eq!(thin.with_arc(Arc::count), 1);
+
+            let clones = [thin.clone(), thin.clone(), thin.clone()];
+            assert_eq!(ThinArc::strong_count(&thin), 4);
+            for clone in &clones {
+                assert_eq!(clone.slice(), &bytes[..]);
+            }
+            drop(clones);
+            assert_eq!(ThinArc::strong_count(&thin), 1);
+            assert_eq!(thin.slice(), &bytes[..]);
+
+            // ZST elements too: only the length tells how many there are
+            let units = ThinArc::from_header_and_iter((), (0..len).map(|_| ()));
+            let clone = units.clone();
+            assert_eq!(ThinArc::strong_count(&units), 2);
+            assert_eq!(clone.len(), len);
+            drop(units);
+            assert_eq!(ThinArc::strong_count(&clone), 1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn thin_zst_header_across_threads() {
+        for len in [0, 1, 9] {
+            let bytes: alloc::vec::Vec<u8> = (0..len as u8).collect();
+            let thin = ThinArc::from_header_and_slice((), &bytes);
+
+            let threads: alloc::vec::Vec<_> = (0..4)
+                .map(|_| {
+                    let thin = thin.clone();
+                    std::thread::spawn(move || {
+                        let clone = thin.clone();
+                        assert_eq!(clone.len(), thin.len());
+                        ThinArc::strong_count(&clone)
+                    })
+                })
+                .collect();
+            for thread in threads {
+                assert!(thread.join().unwrap() >= 2);
+            }
+
+            assert_eq!(ThinArc::strong_count(&thin), 1);
+            assert_eq!(thin.slice(), &bytes[..]);
+        }
+    }
+
+    #[test]
+    fn borrow_thin_and_fat() {
+        fn sum_thin(thin: &ThinArc<u8, u32>) -> u32 {
+            thin.slice().iter().sum()
+        }
+        fn sum_fat(arc: &Arc<crate::HeaderSliceWithLength<u8, [u32]>>) -> u32 {
+            arc.slice.iter().sum()
+        }
+
+        let arc = Arc::from_header_with_length_and_slice(1u8, &[1u32, 2, 3]);
+        let owned = {
+            let thin = arc.borrow_thin();
+            assert_eq!(sum_thin(&thin), 6);
+            assert_eq!(thin.slice(), &arc.slice);
+            assert_eq!(thin.heap_ptr(), arc.heap_ptr());
+            assert_eq!(Arc::count(&arc), 1);
+
+            // Cloning the borrowed `ThinArc` takes a real reference
+            ThinArc::clone(&thin)
+        };
+        assert_eq!(Arc::count(&arc), 2);
+
+        {
+            let fat = owned.borrow_fat();
+            assert_eq!(sum_fat(&fat), 6);
+            assert!(Arc::ptr_eq(&fat, &arc));
+        }
+        assert_eq!(ThinArc::strong_count(&owned), 2);
+
+        drop(owned);
+        assert!(arc.is_unique());
+    }
 }
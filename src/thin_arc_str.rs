@@ -0,0 +1,190 @@
+use core::borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str;
+
+use crate::ThinArc;
+
+/// A thin, atomically reference counted string slice.
+///
+/// This is like an `Arc<str>`, except that it is a single word wide: the length
+/// of the string is stored in the allocation, as with [`ThinArc`]. This is
+/// useful e.g. for the keys of interner tables, where pointer size matters.
+///
+/// ```
+/// use triomphe::ThinArcStr;
+///
+/// let s = ThinArcStr::new("interned");
+/// assert_eq!(&*s, "interned");
+/// assert_eq!(std::mem::size_of::<ThinArcStr>(), std::mem::size_of::<usize>());
+/// ```
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct ThinArcStr(ThinArc<(), u8>);
+
+impl ThinArcStr {
+    /// Creates a new `ThinArcStr` holding a copy of `string`.
+    #[inline]
+    pub fn new(string: &str) -> Self {
+        ThinArcStr(ThinArc::from_header_and_str((), string))
+    }
+
+    /// Returns the contained string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // Safety: the bytes were copied from a `str` in `new`, and are never mutated
+        unsafe { str::from_utf8_unchecked(self.0.slice()) }
+    }
+
+    /// Returns true if the two `ThinArcStr`s point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ThinArc::ptr_eq(&this.0, &other.0)
+    }
+
+    /// The reference count of this `ThinArcStr`.
+    ///
+    /// The function is called `strong_count` to mirror `std::sync::Arc::strong_count`,
+    /// however `triomphe::Arc` does not support weak references.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        ThinArc::strong_count(&this.0)
+    }
+}
+
+impl Deref for ThinArcStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&str> for ThinArcStr {
+    #[inline]
+    fn from(string: &str) -> Self {
+        ThinArcStr::new(string)
+    }
+}
+
+impl Default for ThinArcStr {
+    #[inline]
+    fn default() -> Self {
+        ThinArcStr::new("")
+    }
+}
+
+impl borrow::Borrow<str> for ThinArcStr {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ThinArcStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// The comparison and hashing impls go through `str`, to be consistent with `Borrow<str>`.
+
+impl PartialEq for ThinArcStr {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ThinArcStr::ptr_eq(self, other) || self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for ThinArcStr {}
+
+impl PartialOrd for ThinArcStr {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ThinArcStr {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for ThinArcStr {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl fmt::Debug for ThinArcStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ThinArcStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ThinArcStr;
+    use alloc::string::ToString;
+    use core::mem::size_of;
+
+    #[test]
+    fn thin_arc_str_is_thin() {
+        assert_eq!(size_of::<ThinArcStr>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<ThinArcStr>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn thin_arc_str_round_trip() {
+        for string in ["", "ascii", "héllo wörld", "日本語", "🦀 crab"] {
+            let s = ThinArcStr::new(string);
+            assert_eq!(s.as_str(), string);
+            assert_eq!(&*s, string);
+            assert_eq!(s.len(), string.len());
+            assert_eq!(s.to_string(), string);
+            assert_eq!(s, ThinArcStr::from(string));
+        }
+        assert_eq!(ThinArcStr::default(), ThinArcStr::new(""));
+        assert!(ThinArcStr::new("a") < ThinArcStr::new("b"));
+    }
+
+    #[test]
+    fn thin_arc_str_clone() {
+        let s = ThinArcStr::new("shared");
+        let clone = s.clone();
+        assert!(ThinArcStr::ptr_eq(&s, &clone));
+        assert_eq!(ThinArcStr::strong_count(&s), 2);
+
+        drop(clone);
+        assert_eq!(ThinArcStr::strong_count(&s), 1);
+        assert!(!ThinArcStr::ptr_eq(&s, &ThinArcStr::new("shared")));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn thin_arc_str_lookup_by_str() {
+        use std::collections::HashSet;
+
+        let mut interned = HashSet::new();
+        assert!(interned.insert(ThinArcStr::new("a")));
+        assert!(interned.insert(ThinArcStr::new("b")));
+        assert!(!interned.insert(ThinArcStr::new("a")));
+
+        assert!(interned.contains("a"));
+        assert!(!interned.contains("c"));
+        assert_eq!(interned.get("b").map(ThinArcStr::as_str), Some("b"));
+    }
+}
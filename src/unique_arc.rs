@@ -1,13 +1,12 @@
 use alloc::vec::Vec;
 use alloc::{alloc::Layout, boxed::Box};
 use core::convert::TryFrom;
-use core::iter::FromIterator;
-use core::marker::PhantomData;
+use core::iter::{ExactSizeIterator, FromIterator, Iterator};
 use core::mem::{ManuallyDrop, MaybeUninit};
 use core::ops::{Deref, DerefMut};
 use core::ptr::{self, addr_of_mut, NonNull};
-use core::sync::atomic::AtomicUsize;
 
+use crate::atomic::AtomicUsize;
 use crate::iterator_as_exact_size_iterator::IteratorAsExactSizeIterator;
 use crate::HeaderSlice;
 
@@ -36,6 +35,13 @@ use super::{Arc, ArcInner};
 /// x[4] = 7; // mutate!
 /// let y = x.shareable(); // y is an Arc<T>
 /// ```
+///
+/// The refcount of a `UniqueArc` is always 1, and no other pointer to its
+/// allocation exists, so `&mut self` gives exclusive access to the data, as with
+/// a `Box`. Mutable references are derived from the pointer to the allocation,
+/// not from a shared reference to it, so they are valid for writes even under
+/// Stacked Borrows. Once shared, the data is only ever accessed through shared
+/// references again.
 #[repr(transparent)]
 pub struct UniqueArc<T: ?Sized>(Arc<T>);
 
@@ -57,15 +63,12 @@ impl<T> UniqueArc<T> {
         unsafe {
             let layout = Layout::new::<ArcInner<MaybeUninit<T>>>();
             let ptr = alloc::alloc::alloc(layout);
-            let mut p = NonNull::new(ptr)
+            let p = NonNull::new(ptr)
                 .unwrap_or_else(|| alloc::alloc::handle_alloc_error(layout))
                 .cast::<ArcInner<MaybeUninit<T>>>();
-            ptr::write(&mut p.as_mut().count, AtomicUsize::new(1));
+            ptr::write(addr_of_mut!((*p.as_ptr()).count), AtomicUsize::new(1));
 
-            UniqueArc(Arc {
-                p,
-                phantom: PhantomData,
-            })
+            UniqueArc(Arc::from_inner(p))
         }
     }
 
@@ -87,11 +90,45 @@ impl<T> UniqueArc<T> {
 
 impl<T: ?Sized> UniqueArc<T> {
     /// Convert to a shareable `Arc<T>` once we're done mutating it
+    ///
+    /// Until then, the data can be mutated freely through `DerefMut`.
     #[inline]
     pub fn shareable(self) -> Arc<T> {
         self.0
     }
 
+    /// Returns a raw pointer to the data, which stays the same once shared.
+    ///
+    /// This is the pointer that [`Arc::as_ptr`] returns for the `Arc` obtained with
+    /// [`shareable`](UniqueArc::shareable), and that [`assume_init`](UniqueArc::assume_init)
+    /// keeps (cast to `*const T`). This allows e.g. building values which store
+    /// their own final address, or those of each other, before they are shared:
+    ///
+    /// ```
+    /// use std::mem::MaybeUninit;
+    /// use triomphe::{Arc, UniqueArc};
+    ///
+    /// struct Node {
+    ///     this: *const Node,
+    /// }
+    ///
+    /// let mut node = UniqueArc::<Node>::new_uninit();
+    /// let this = UniqueArc::as_ptr(&node) as *const Node;
+    /// node.write(Node { this });
+    /// let node: Arc<Node> = unsafe { UniqueArc::assume_init(node) }.shareable();
+    /// assert_eq!(Arc::as_ptr(&node), node.this);
+    /// ```
+    ///
+    /// The pointer may only be dereferenced while the allocation is alive, and
+    /// not while the data is mutably borrowed through the `UniqueArc`.
+    ///
+    /// This is an associated function, so that it doesn't shadow e.g. the
+    /// `as_ptr` method of slices.
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *const T {
+        this.0.as_ptr()
+    }
+
     /// Creates a new [`UniqueArc`] from the given [`Arc`].
     ///
     /// An unchecked alternative to `Arc::try_unique()`
@@ -150,10 +187,7 @@ impl<T> UniqueArc<MaybeUninit<T>> {
     /// has actually been initialized before calling this method.
     #[inline]
     pub unsafe fn assume_init(this: Self) -> UniqueArc<T> {
-        UniqueArc(Arc {
-            p: ManuallyDrop::new(this).0.p.cast(),
-            phantom: PhantomData,
-        })
+        UniqueArc(Arc::from_inner(ManuallyDrop::new(this).0.p.cast()))
     }
 }
 
@@ -179,6 +213,40 @@ impl<T> UniqueArc<[MaybeUninit<T>]> {
     }
 }
 
+impl<T> UniqueArc<[T]> {
+    /// Obtain a mutable slice to the stored `[T]`.
+    ///
+    /// This is the same as going through `DerefMut`, but spelled out.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<H, T> UniqueArc<HeaderSlice<H, [T]>> {
+    /// Creates a `UniqueArc` for a HeaderSlice using the given header struct and
+    /// iterator to generate the slice.
+    #[inline]
+    pub fn from_header_and_iter<I>(header: H, items: I) -> Self
+    where
+        I: Iterator<Item = T> + ExactSizeIterator,
+    {
+        // Safety: We just created an `Arc`, so it's unique.
+        unsafe { UniqueArc::from_arc(Arc::from_header_and_iter(header, items)) }
+    }
+
+    /// Creates a `UniqueArc` for a HeaderSlice using the given header struct and
+    /// a slice to copy.
+    #[inline]
+    pub fn from_header_and_slice(header: H, items: &[T]) -> Self
+    where
+        T: Copy,
+    {
+        // Safety: We just created an `Arc`, so it's unique.
+        unsafe { UniqueArc::from_arc(Arc::from_header_and_slice(header, items)) }
+    }
+}
+
 impl<H, T> UniqueArc<HeaderSlice<H, [MaybeUninit<T>]>> {
     /// Creates an Arc for a HeaderSlice using the given header struct and allocated space
     /// for an unitialized slice of length `len`.
@@ -196,10 +264,7 @@ impl<H, T> UniqueArc<HeaderSlice<H, [MaybeUninit<T>]>> {
 
         // Safety: ptr is valid & the inner structure is initialized.
         // We wrote the header above and the slice can stay unitialized as it's [MaybeUninit<T>]
-        Self(Arc {
-            p: inner,
-            phantom: PhantomData,
-        })
+        Self(unsafe { Arc::from_inner(inner) })
     }
 
     /// # Safety
@@ -231,7 +296,8 @@ impl<T: ?Sized> Deref for UniqueArc<T> {
 impl<T: ?Sized> DerefMut for UniqueArc<T> {
     #[inline]
     fn deref_mut(&mut self) -> &mut T {
-        // We know this to be uniquely owned
+        // We know this to be uniquely owned, and `ptr()` has the provenance of
+        // the whole allocation, rather than that of a `&ArcInner<T>`
         unsafe { &mut (*self.0.ptr()).data }
     }
 }
@@ -277,6 +343,8 @@ unsafe impl<T, U: ?Sized> unsize::CoerciblePtr<U> for UniqueArc<T> {
 #[cfg(test)]
 mod tests {
     use crate::{Arc, HeaderSliceWithLength, HeaderWithLength, UniqueArc};
+    use alloc::string::String;
+    use core::ptr::addr_of_mut;
     use core::{convert::TryFrom, mem::MaybeUninit};
 
     #[test]
@@ -307,10 +375,59 @@ mod tests {
         assert_eq!(*arc, 999);
     }
 
+    #[test]
+    fn unique_from_header_and_iter() {
+        let mut uarc = UniqueArc::from_header_and_iter("sorted", [3u32, 1, 2].iter().copied());
+        uarc.slice.sort_unstable();
+        uarc.header = "really sorted";
+
+        let arc = uarc.shareable();
+        assert_eq!(arc.header, "really sorted");
+        assert_eq!(arc.slice, [1, 2, 3]);
+    }
+
+    #[test]
+    fn unique_from_header_and_slice() {
+        let mut uarc = UniqueArc::from_header_and_slice((), &[3u32, 1, 2]);
+        uarc.slice.sort_unstable();
+
+        let arc = uarc.shareable();
+        assert!(arc.is_unique());
+        assert_eq!(arc.slice, [1, 2, 3]);
+    }
+
+    #[test]
+    fn maybeuninit_field_by_field() {
+        struct Large {
+            id: u64,
+            name: String,
+            data: [u8; 4096],
+        }
+
+        let mut uarc: UniqueArc<MaybeUninit<Large>> = UniqueArc::new_uninit();
+        let large = MaybeUninit::as_mut_ptr(&mut *uarc);
+        unsafe {
+            addr_of_mut!((*large).id).write(7);
+            addr_of_mut!((*large).name).write(String::from("large"));
+            addr_of_mut!((*large).data).write_bytes(1, 1);
+        }
+
+        let uarc = unsafe { UniqueArc::assume_init(uarc) };
+        assert_eq!(uarc.id, 7);
+        assert_eq!(uarc.name, "large");
+        assert!(uarc.data.iter().all(|&b| b == 1));
+    }
+
     #[test]
     fn from_header_and_uninit_slice() {
         let mut uarc: UniqueArc<HeaderSliceWithLength<u8, [MaybeUninit<u16>]>> =
-            UniqueArc::from_header_and_uninit_slice(HeaderWithLength::new(1, 3), 3);
+            UniqueArc::from_header_and_uninit_slice(
+                HeaderWithLength {
+                    header: 1,
+                    length: 3,
+                },
+                3,
+            );
         uarc.slice.fill(MaybeUninit::new(2));
         let arc = unsafe { uarc.assume_init_slice_with_header() }.shareable();
         assert!(arc.is_unique());
@@ -340,4 +457,112 @@ mod tests {
         assert_eq!(arc.header.header, 1);
         assert_eq!(&arc.slice, [2, 2, 2]);
     }
+
+    #[test]
+    fn cross_references_before_sharing() {
+        struct Node {
+            name: &'static str,
+            other: *const Node,
+        }
+
+        let mut a = UniqueArc::<Node>::new_uninit();
+        let mut b = UniqueArc::<Node>::new_uninit();
+        let a_ptr = UniqueArc::as_ptr(&a) as *const Node;
+        let b_ptr = UniqueArc::as_ptr(&b) as *const Node;
+        a.write(Node {
+            name: "a",
+            other: b_ptr,
+        });
+        b.write(Node {
+            name: "b",
+            other: a_ptr,
+        });
+
+        // Safety: both were written
+        let a = unsafe { UniqueArc::assume_init(a) }.shareable();
+        let b = unsafe { UniqueArc::assume_init(b) }.shareable();
+        assert_eq!(Arc::as_ptr(&a), a_ptr);
+        assert_eq!(Arc::as_ptr(&b.clone()), b_ptr);
+
+        // Safety: both are alive, and no longer mutably borrowed
+        unsafe {
+            assert_eq!((*a.other).name, "b");
+            assert_eq!((*b.other).name, "a");
+            assert_eq!((*(*a.other).other).name, "a");
+        }
+    }
+
+    #[test]
+    fn mutate_then_share() {
+        let mut unique = UniqueArc::new(String::from("hello"));
+        unique.push_str(", world");
+        let arc = unique.shareable();
+        let clone = arc.clone();
+        assert!(Arc::ptr_eq(&arc, &clone));
+        assert_eq!(*clone, "hello, world");
+
+        let mut slice: UniqueArc<[u32]> = (0..4).collect();
+        slice.as_mut_slice()[1] = 10;
+        slice.reverse();
+        let arc = slice.shareable();
+        assert_eq!(&*arc.clone(), [3, 2, 10, 0]);
+    }
+
+    // Mostly useful under miri, which checks that the `&mut` handed out while
+    // unique don't conflict with the shared references used afterwards.
+    #[test]
+    fn unique_to_shared_transitions() {
+        let mut unique = UniqueArc::new([0u32; 4]);
+        let data: &mut [u32; 4] = &mut unique;
+        data[0] = 1;
+        let data_ptr: *const [u32; 4] = data;
+        unique[1] = 2;
+        let arc = unique.shareable();
+        let clone = arc.clone();
+        assert_eq!(Arc::as_ptr(&arc), data_ptr);
+        assert_eq!(*clone, [1, 2, 0, 0]);
+
+        // Back to unique, through the `Arc`
+        drop(clone);
+        let mut arc = arc;
+        let shared = Arc::as_ptr(&arc);
+        Arc::get_mut(&mut arc).unwrap()[2] = 3;
+        Arc::get_unique(&mut arc).unwrap()[3] = 4;
+        // Safety: the allocation is alive, and no `&mut` is
+        unsafe { assert_eq!(*shared, [1, 2, 3, 4]) };
+
+        // `make_unique` on a shared `Arc` mutates a copy
+        let mut copy = arc.clone();
+        Arc::make_unique(&mut copy)[0] = 10;
+        assert_eq!(*arc, [1, 2, 3, 4]);
+        assert_eq!(*copy, [10, 2, 3, 4]);
+        assert!(arc.is_unique() && copy.is_unique());
+
+        // ...and the original in place otherwise
+        Arc::make_unique(&mut arc)[0] = 5;
+        assert_eq!(Arc::as_ptr(&arc), shared);
+        let unique = UniqueArc::try_from(arc).unwrap();
+        assert_eq!(UniqueArc::into_inner(unique), [5, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn mutate_then_share_across_threads() {
+        let mut unique = UniqueArc::new(alloc::vec![0u64; 16]);
+        for (i, x) in unique.iter_mut().enumerate() {
+            *x = i as u64;
+        }
+        let arc = unique.shareable();
+
+        let threads: alloc::vec::Vec<_> = (0..4)
+            .map(|_| {
+                let arc = arc.clone();
+                std::thread::spawn(move || arc.iter().sum::<u64>())
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), 120);
+        }
+        assert!(arc.is_unique());
+    }
 }
@@ -0,0 +1,382 @@
+//! An `Arc` variant supporting weak references.
+//!
+//! [`weak::Arc`](Arc) stores a weak count next to the strong one, making each
+//! allocation one word larger than a [`triomphe::Arc`](crate::Arc). Prefer the
+//! latter unless [`Weak`] references are actually needed.
+
+use alloc::alloc::dealloc;
+use alloc::boxed::Box;
+use core::alloc::Layout;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::ptr::{addr_of_mut, NonNull};
+
+use crate::abort;
+use crate::arc::{decrement, increment, MAX_REFCOUNT};
+use crate::atomic;
+use crate::atomic::Ordering::{Acquire, Relaxed};
+
+/// The object allocated by a weak-capable `Arc<T>`.
+///
+/// All strong references collectively hold a single weak reference, so that
+/// `weak` only reaches zero once the data has been dropped.
+#[repr(C)]
+struct WeakInner<T> {
+    strong: atomic::AtomicUsize,
+    weak: atomic::AtomicUsize,
+    data: ManuallyDrop<T>,
+}
+
+/// An atomically reference counted shared pointer, like [`triomphe::Arc`](crate::Arc),
+/// that additionally supports [`Weak`] references.
+pub struct Arc<T> {
+    p: NonNull<WeakInner<T>>,
+    phantom: PhantomData<T>,
+}
+
+unsafe impl<T: Sync + Send> Send for Arc<T> {}
+unsafe impl<T: Sync + Send> Sync for Arc<T> {}
+
+/// A non-owning reference to the data of a weak-capable [`Arc`].
+///
+/// It doesn't keep the data alive, and must be [upgraded](Weak::upgrade) to access it.
+pub struct Weak<T> {
+    /// `None` for a `Weak` created by [`Weak::new`], which has no allocation.
+    p: Option<NonNull<WeakInner<T>>>,
+    phantom: PhantomData<T>,
+}
+
+unsafe impl<T: Sync + Send> Send for Weak<T> {}
+unsafe impl<T: Sync + Send> Sync for Weak<T> {}
+
+impl<T> Arc<T> {
+    /// Constructs a new `Arc`.
+    #[inline]
+    pub fn new(data: T) -> Self {
+        let inner = Box::new(WeakInner {
+            strong: atomic::AtomicUsize::new(1),
+            weak: atomic::AtomicUsize::new(1),
+            data: ManuallyDrop::new(data),
+        });
+
+        Arc {
+            // Safety: `Box::into_raw` is never null
+            p: unsafe { NonNull::new_unchecked(Box::into_raw(inner)) },
+            phantom: PhantomData,
+        }
+    }
+
+    /// Creates a new [`Weak`] pointer to this allocation.
+    #[inline]
+    pub fn downgrade(this: &Self) -> Weak<T> {
+        // Like in `Clone`, we hold a strong reference, so the allocation can't go
+        // away concurrently.
+        increment(&this.inner().weak);
+
+        Weak {
+            p: Some(this.p),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Gets the number of strong (`Arc`) pointers to this allocation.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.inner().strong.load(Acquire)
+    }
+
+    /// Gets the number of [`Weak`] pointers to this allocation.
+    #[inline]
+    pub fn weak_count(this: &Self) -> usize {
+        // Don't count the weak reference held by the strong ones
+        this.inner().weak.load(Acquire) - 1
+    }
+
+    /// Returns `true` if the two `Arc`s point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.p == other.p
+    }
+
+    #[inline]
+    fn inner(&self) -> &WeakInner<T> {
+        // Safety: we hold a strong reference, so the allocation is alive
+        unsafe { self.p.as_ref() }
+    }
+
+    #[inline(never)]
+    unsafe fn drop_slow(&mut self) {
+        // Safety: this was the last strong reference, so nobody else can access the data
+        ManuallyDrop::drop(&mut *addr_of_mut!((*self.p.as_ptr()).data));
+
+        // Release the weak reference collectively held by the strong ones.
+        drop(Weak {
+            p: Some(self.p),
+            phantom: PhantomData,
+        });
+    }
+}
+
+impl<T> Clone for Arc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        increment(&self.inner().strong);
+
+        Arc {
+            p: self.p,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for Arc<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.inner().data
+    }
+}
+
+impl<T> Drop for Arc<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if !decrement(&self.inner().strong) {
+            return;
+        }
+
+        unsafe { self.drop_slow() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Arc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Weak<T> {
+    /// Constructs a new `Weak<T>`, without allocating any memory.
+    /// Calling [`upgrade`](Weak::upgrade) on it always gives `None`.
+    #[inline]
+    pub const fn new() -> Self {
+        Weak {
+            p: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Attempts to upgrade the `Weak` pointer to an [`Arc`], returning `None`
+    /// if the data has been dropped.
+    pub fn upgrade(&self) -> Option<Arc<T>> {
+        let p = self.p?;
+        let (strong, _) = self.counts()?;
+
+        // We can't use `fetch_add` as in `Arc::clone`, since the strong count
+        // must never be brought back up from zero.
+        let mut n = strong.load(Relaxed);
+        loop {
+            if n == 0 {
+                return None;
+            }
+            if n > MAX_REFCOUNT {
+                abort();
+            }
+
+            // Acquire synchronizes with the `Release` decrement of a dropped
+            // `Arc`, so that we see its writes to the data.
+            match strong.compare_exchange_weak(n, n + 1, Acquire, Relaxed) {
+                Ok(_) => {
+                    return Some(Arc {
+                        p,
+                        phantom: PhantomData,
+                    })
+                }
+                Err(old) => n = old,
+            }
+        }
+    }
+
+    /// Gets the number of strong (`Arc`) pointers to this allocation,
+    /// or 0 if it was created by [`Weak::new`].
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.counts().map_or(0, |(strong, _)| strong.load(Acquire))
+    }
+
+    /// Returns `true` if the two `Weak`s point to the same allocation,
+    /// or if both were created by [`Weak::new`].
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.p == other.p
+    }
+
+    /// Returns the strong and weak counts, unless this was created by [`Weak::new`].
+    ///
+    /// This doesn't go through a reference to the whole `WeakInner`, since the
+    /// data may have been dropped already.
+    #[inline]
+    fn counts(&self) -> Option<(&atomic::AtomicUsize, &atomic::AtomicUsize)> {
+        // Safety: we hold a weak reference, so the allocation (but not
+        // necessarily the data) is alive
+        self.p
+            .map(|p| unsafe { (&(*p.as_ptr()).strong, &(*p.as_ptr()).weak) })
+    }
+}
+
+impl<T> Clone for Weak<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        if let Some((_, weak)) = self.counts() {
+            increment(weak);
+        }
+
+        Weak {
+            p: self.p,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for Weak<T> {
+    #[inline]
+    fn default() -> Self {
+        Weak::new()
+    }
+}
+
+impl<T> Drop for Weak<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let p = match self.p {
+            Some(p) => p,
+            None => return,
+        };
+
+        // Safety: we hold a weak reference, so the allocation is alive
+        if !decrement(unsafe { &(*p.as_ptr()).weak }) {
+            return;
+        }
+
+        // Safety: this was the last reference of any kind, and the data has
+        // already been dropped, so only the memory is left to free. It was
+        // allocated by a `Box` in `Arc::new`.
+        unsafe { dealloc(p.as_ptr() as *mut u8, Layout::new::<WeakInner<T>>()) }
+    }
+}
+
+impl<T> fmt::Debug for Weak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(Weak)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Arc, Weak};
+    use crate::test_drops::DropCounter;
+    use alloc::string::String;
+
+    #[test]
+    fn upgrade_and_drop() {
+        let arc = Arc::new(String::from("hello"));
+        let weak = Arc::downgrade(&arc);
+        assert_eq!(Arc::strong_count(&arc), 1);
+        assert_eq!(Arc::weak_count(&arc), 1);
+
+        let upgraded = weak.upgrade().unwrap();
+        assert_eq!(*upgraded, "hello");
+        assert!(Arc::ptr_eq(&arc, &upgraded));
+        assert_eq!(weak.strong_count(), 2);
+
+        drop(arc);
+        drop(upgraded);
+        assert_eq!(weak.strong_count(), 0);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn weak_new() {
+        let weak: Weak<String> = Weak::new();
+        assert!(weak.upgrade().is_none());
+        assert_eq!(weak.strong_count(), 0);
+        assert!(weak.ptr_eq(&weak.clone()));
+        assert!(Weak::<String>::default().upgrade().is_none());
+    }
+
+    #[test]
+    fn data_dropped_once_before_weak() {
+        let drops = DropCounter::new();
+        let arc = Arc::new(drops.counted(()));
+        let weak = Arc::downgrade(&arc);
+        let weak2 = weak.clone();
+        let arc2 = arc.clone();
+
+        drop(arc);
+        assert_eq!(drops.drops(), 0);
+        drop(arc2);
+        assert_eq!(drops.drops(), 1);
+
+        drop(weak);
+        assert!(weak2.upgrade().is_none());
+        drop(weak2);
+        assert_eq!(drops.drops(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn upgrade_drop_race() {
+        const ROUNDS: usize = 1000;
+        let drops = DropCounter::new();
+        for i in 0..ROUNDS {
+            let arc = Arc::new(drops.counted(i));
+            let weak = Arc::downgrade(&arc);
+
+            let upgrader = std::thread::spawn(move || {
+                if let Some(arc) = weak.upgrade() {
+                    // The data is alive for as long as we hold the upgraded `Arc`
+                    assert_eq!(arc.0, i);
+                }
+                weak
+            });
+            drop(arc);
+
+            let weak = upgrader.join().unwrap();
+            assert!(weak.upgrade().is_none());
+        }
+
+        assert_eq!(drops.drops(), ROUNDS);
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn loom_upgrade_drop_race() {
+        use loom::cell::UnsafeCell;
+
+        struct Payload(UnsafeCell<usize>);
+        impl Drop for Payload {
+            fn drop(&mut self) {
+                // Races with the other thread's write unless the orderings of
+                // `upgrade` and `Drop` make it happen before.
+                self.0.with(|v| assert!(unsafe { *v } <= 1));
+            }
+        }
+
+        loom::model(|| {
+            let arc = Arc::new(Payload(UnsafeCell::new(0)));
+            let weak = Arc::downgrade(&arc);
+
+            let thread = loom::thread::spawn(move || {
+                if let Some(arc) = weak.upgrade() {
+                    // Safety: the other thread only accesses the value to drop it,
+                    // which can't happen while we hold `arc`
+                    arc.0.with_mut(|v| unsafe { *v = 1 });
+                }
+            });
+            drop(arc);
+            thread.join().unwrap();
+        });
+    }
+}
@@ -0,0 +1,108 @@
+//! Serialization format tests. They live outside of the crate's unit tests,
+//! where the `PartialEq` impls of `serde_json::Value` for primitives would make
+//! comparisons such as `slice == []` ambiguous.
+
+#![cfg(feature = "serde")]
+
+extern crate bincode;
+extern crate serde_json;
+extern crate triomphe;
+
+use triomphe::{Arc, HeaderSlice, ThinArc};
+
+#[test]
+fn serde_header_slice() {
+    let arc = Arc::from_header_and_slice(7u32, &[1u8, 2, 3]);
+    let json = serde_json::to_string(&arc).unwrap();
+    assert_eq!(json, r#"{"header":7,"slice":[1,2,3]}"#);
+    let back: Arc<HeaderSlice<u32, [u8]>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, arc);
+
+    let bytes = bincode::serialize(&arc).unwrap();
+    assert_eq!(bytes, bincode::serialize(&(7u32, vec![1u8, 2, 3])).unwrap());
+    let back: Arc<HeaderSlice<u32, [u8]>> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(back, arc);
+
+    let empty = Arc::from_header_and_slice(String::from("e"), &[] as &[u8]);
+    let json = serde_json::to_string(&empty).unwrap();
+    let back: Arc<HeaderSlice<String, [u8]>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, empty);
+}
+
+#[test]
+fn serde_header_str() {
+    let arc = Arc::from_header_and_str(5u8, "hello");
+    let json = serde_json::to_string(&arc).unwrap();
+    assert_eq!(json, r#"{"header":5,"slice":"hello"}"#);
+    let back: Arc<HeaderSlice<u8, str>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, arc);
+
+    let bytes = bincode::serialize(&arc).unwrap();
+    let back: Arc<HeaderSlice<u8, str>> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(back, arc);
+}
+
+#[test]
+fn serde_header_slice_forms() {
+    type Ha = Arc<HeaderSlice<u32, [u8]>>;
+    let expected = Arc::from_header_and_slice(1u32, &[2u8, 3]);
+
+    // A sequence, like a tuple
+    assert_eq!(serde_json::from_str::<Ha>("[1,[2,3]]").unwrap(), expected);
+    // Fields in any order, unknown ones skipped
+    let json = r#"{"slice":[2,3],"extra":{"a":null},"header":1}"#;
+    assert_eq!(serde_json::from_str::<Ha>(json).unwrap(), expected);
+}
+
+#[test]
+fn serde_header_slice_errors() {
+    type Ha = Arc<HeaderSlice<u32, [u8]>>;
+    let err = |json| serde_json::from_str::<Ha>(json).unwrap_err().to_string();
+
+    assert!(err(r#"{"header":1,"header":2,"slice":[]}"#).contains("duplicate field `header`"));
+    assert!(err(r#"{"header":1,"slice":[],"slice":[]}"#).contains("duplicate field `slice`"));
+    assert!(err(r#"{"slice":[]}"#).contains("missing field `header`"));
+    assert!(err(r#"{"header":1}"#).contains("missing field `slice`"));
+    assert!(err("[1]").contains("invalid length 1"));
+    assert!(err("[]").contains("invalid length 0"));
+    assert!(err("1").contains("struct HeaderSlice"));
+
+    let err = serde_json::from_str::<Arc<HeaderSlice<u8, str>>>(r#"{"header":1,"slice":[]}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("expected a string"));
+}
+
+#[test]
+fn thin_serde_roundtrip() {
+    let thin = ThinArc::from_header_and_slice(String::from("h"), &[1u32, 2, 3]);
+    let json = serde_json::to_string(&thin).unwrap();
+    assert_eq!(json, r#"{"header":"h","slice":[1,2,3]}"#);
+    let back: ThinArc<String, u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, thin);
+    assert_eq!(back.len(), 3);
+
+    let bytes = bincode::serialize(&thin).unwrap();
+    assert_eq!(bytes, bincode::serialize(&("h", vec![1u32, 2, 3])).unwrap());
+    let back: ThinArc<String, u32> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(back, thin);
+
+    // The same format as the fat `Arc`
+    let fat = Arc::from_header_and_slice(String::from("h"), &[1u32, 2, 3]);
+    assert_eq!(serde_json::to_string(&fat).unwrap(), json);
+}
+
+#[test]
+fn thin_serde_empty() {
+    let thin = ThinArc::from_header_and_slice(9u8, &[] as &[u16]);
+    let json = serde_json::to_string(&thin).unwrap();
+    assert_eq!(json, r#"{"header":9,"slice":[]}"#);
+    let back: ThinArc<u8, u16> = serde_json::from_str(&json).unwrap();
+    assert_eq!(*back.header(), 9);
+    assert!(back.is_empty());
+
+    let bytes = bincode::serialize(&thin).unwrap();
+    let back: ThinArc<u8, u16> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(back, thin);
+    assert_eq!(back.len(), 0);
+}